@@ -0,0 +1,137 @@
+use bigint::BigInt;
+use std::fmt;
+
+/// One of the integer types, which differ in how many bits wide they are and whether they can
+/// hold negative numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntTy {
+    I8,
+    I16,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl IntTy {
+    /// Looks up the type with the name `name`, which is also the suffix of a literal of that
+    /// type, as in `5u8`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let ty = match name {
+            "i8" => Self::I8,
+            "i16" => Self::I16,
+            "i32" => Self::I32,
+            "i64" => Self::I64,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            _ => return None,
+        };
+
+        Some(ty)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::I8 => "i8",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            Self::I8 | Self::U8 => 8,
+            Self::I16 | Self::U16 => 16,
+            Self::I32 | Self::U32 => 32,
+            Self::I64 | Self::U64 => 64,
+        }
+    }
+
+    pub fn is_signed(self) -> bool {
+        matches!(self, Self::I8 | Self::I16 | Self::I32 | Self::I64)
+    }
+
+    /// The smallest value of this type. Every type’s values fit in an `i128`.
+    pub fn min(self) -> i128 {
+        if self.is_signed() {
+            -(1 << (self.bits() - 1))
+        } else {
+            0
+        }
+    }
+
+    /// The largest value of this type.
+    pub fn max(self) -> i128 {
+        if self.is_signed() {
+            (1 << (self.bits() - 1)) - 1
+        } else {
+            (1 << self.bits()) - 1
+        }
+    }
+
+    pub fn contains(self, value: i128) -> bool {
+        (self.min()..=self.max()).contains(&value)
+    }
+
+    /// The value of the literal `n` of this type, or `None` if it doesn’t fit. If the literal is
+    /// `negated`, the negation is taken as a whole, so that the smallest value of a signed type
+    /// can be written even though its magnitude is larger than the type’s maximum. Negating an
+    /// unsigned literal is a type error that is reported separately, so the literal is checked
+    /// as if it weren’t negated.
+    pub fn literal_value(self, n: &BigInt, negated: bool) -> Option<i128> {
+        let value = if self.is_signed() && negated {
+            -n
+        } else {
+            n.clone()
+        };
+
+        value.to_i128().filter(|value| self.contains(*value))
+    }
+}
+
+impl fmt::Display for IntTy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranges_match_rust_types() {
+        assert_eq!((IntTy::I8.min(), IntTy::I8.max()), (-128, 127));
+        assert_eq!(IntTy::I64.min(), i64::MIN.into());
+        assert_eq!(IntTy::I64.max(), i64::MAX.into());
+        assert_eq!((IntTy::U16.min(), IntTy::U16.max()), (0, 65535));
+        assert_eq!(IntTy::U64.max(), u64::MAX.into());
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for ty in &[IntTy::I32, IntTy::U8] {
+            assert_eq!(IntTy::from_name(ty.name()), Some(*ty));
+        }
+
+        assert_eq!(IntTy::from_name("Int"), None);
+    }
+
+    #[test]
+    fn negated_literals_are_checked_as_a_whole() {
+        let n = BigInt::from(128);
+
+        assert_eq!(IntTy::I8.literal_value(&n, false), None);
+        assert_eq!(IntTy::I8.literal_value(&n, true), Some(-128));
+        assert_eq!(IntTy::U8.literal_value(&n, true), Some(128));
+    }
+}
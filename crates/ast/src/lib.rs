@@ -1,6 +1,28 @@
+mod int_ty;
 pub mod validation;
 
+pub use int_ty::IntTy;
+
+use bigint::BigInt;
 use syntax::{SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken};
+use text_size::TextRange;
+
+/// Returns the range of `node` without any leading or trailing whitespace and comments, which the
+/// parser attaches to the nodes around them.
+pub fn trimmed_range(node: &SyntaxNode) -> TextRange {
+    let mut tokens = node
+        .descendants_with_tokens()
+        .filter_map(SyntaxElement::into_token)
+        .filter(|token| !token.kind().is_trivia());
+
+    match tokens.next() {
+        Some(first) => {
+            let last = tokens.last().unwrap_or_else(|| first.clone());
+            TextRange::new(first.text_range().start(), last.text_range().end())
+        }
+        None => TextRange::empty(node.text_range().start()),
+    }
+}
 
 #[derive(Debug)]
 pub struct Root(SyntaxNode);
@@ -21,14 +43,22 @@ impl Root {
 
 #[derive(Debug)]
 pub enum Stmt {
+    Break(Break),
+    Continue(Continue),
+    FnDef(FnDef),
     VariableDef(VariableDef),
+    WhileLoop(WhileLoop),
     Expr(Expr),
 }
 
 impl Stmt {
     pub fn cast(node: SyntaxNode) -> Option<Self> {
         let result = match node.kind() {
+            SyntaxKind::Break => Self::Break(Break(node)),
+            SyntaxKind::Continue => Self::Continue(Continue(node)),
+            SyntaxKind::FnDef => Self::FnDef(FnDef(node)),
             SyntaxKind::VariableDef => Self::VariableDef(VariableDef(node)),
+            SyntaxKind::WhileLoop => Self::WhileLoop(WhileLoop(node)),
             _ => Self::Expr(Expr::cast(node)?),
         };
 
@@ -36,10 +66,95 @@ impl Stmt {
     }
 }
 
+#[derive(Debug)]
+pub struct Break(SyntaxNode);
+
+impl Break {
+    pub fn keyword(&self) -> SyntaxToken {
+        self.0.first_token().unwrap()
+    }
+}
+
+#[derive(Debug)]
+pub struct Continue(SyntaxNode);
+
+impl Continue {
+    pub fn keyword(&self) -> SyntaxToken {
+        self.0.first_token().unwrap()
+    }
+}
+
+#[derive(Debug)]
+pub struct FnDef(SyntaxNode);
+
+impl FnDef {
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Ident)
+    }
+
+    pub fn param_list(&self) -> Option<ParamList> {
+        self.0.children().find_map(ParamList::cast)
+    }
+
+    pub fn body(&self) -> Option<BlockExpr> {
+        self.0
+            .children()
+            .find(|node| node.kind() == SyntaxKind::BlockExpr)
+            .map(BlockExpr)
+    }
+}
+
+#[derive(Debug)]
+pub struct ParamList(SyntaxNode);
+
+impl ParamList {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if node.kind() == SyntaxKind::ParamList {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = Param> {
+        self.0.children().filter_map(Param::cast)
+    }
+}
+
+#[derive(Debug)]
+pub struct Param(SyntaxNode);
+
+impl Param {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if node.kind() == SyntaxKind::Param {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.0.first_token()
+    }
+
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        self.0.children().find_map(TypeRef::cast)
+    }
+}
+
 #[derive(Debug)]
 pub struct VariableDef(SyntaxNode);
 
 impl VariableDef {
+    pub fn is_mutable(&self) -> bool {
+        self.0
+            .children_with_tokens()
+            .any(|element| element.kind() == SyntaxKind::MutKw)
+    }
+
     pub fn name(&self) -> Option<SyntaxToken> {
         self.0
             .children_with_tokens()
@@ -47,14 +162,52 @@ impl VariableDef {
             .find(|token| token.kind() == SyntaxKind::Ident)
     }
 
+    pub fn type_ref(&self) -> Option<TypeRef> {
+        self.0.children().find_map(TypeRef::cast)
+    }
+
     pub fn value(&self) -> Option<Expr> {
         self.0.children().find_map(Expr::cast)
     }
 }
 
+#[derive(Debug)]
+pub struct TypeRef(SyntaxNode);
+
+impl TypeRef {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if node.kind() == SyntaxKind::TypeRef {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> Option<SyntaxToken> {
+        self.0.first_token()
+    }
+}
+
+#[derive(Debug)]
+pub struct WhileLoop(SyntaxNode);
+
+impl WhileLoop {
+    pub fn condition(&self) -> Option<Expr> {
+        condition_and_block(self.0.children().filter_map(Expr::cast)).0
+    }
+
+    pub fn body(&self) -> Option<Expr> {
+        condition_and_block(self.0.children().filter_map(Expr::cast)).1
+    }
+}
+
 #[derive(Debug)]
 pub enum Expr {
+    AssignExpr(AssignExpr),
     BinaryExpr(BinaryExpr),
+    BlockExpr(BlockExpr),
+    CallExpr(CallExpr),
+    IfExpr(IfExpr),
     Literal(Literal),
     ParenExpr(ParenExpr),
     UnaryExpr(UnaryExpr),
@@ -64,7 +217,11 @@ pub enum Expr {
 impl Expr {
     pub fn cast(node: SyntaxNode) -> Option<Self> {
         let result = match node.kind() {
+            SyntaxKind::AssignExpr => Self::AssignExpr(AssignExpr(node)),
             SyntaxKind::InfixExpr => Self::BinaryExpr(BinaryExpr(node)),
+            SyntaxKind::BlockExpr => Self::BlockExpr(BlockExpr(node)),
+            SyntaxKind::CallExpr => Self::CallExpr(CallExpr(node)),
+            SyntaxKind::IfExpr => Self::IfExpr(IfExpr(node)),
             SyntaxKind::Literal => Self::Literal(Literal(node)),
             SyntaxKind::ParenExpr => Self::ParenExpr(ParenExpr(node)),
             SyntaxKind::PrefixExpr => Self::UnaryExpr(UnaryExpr(node)),
@@ -74,6 +231,50 @@ impl Expr {
 
         Some(result)
     }
+
+    pub fn syntax(&self) -> &SyntaxNode {
+        match self {
+            Self::AssignExpr(AssignExpr(node))
+            | Self::BinaryExpr(BinaryExpr(node))
+            | Self::BlockExpr(BlockExpr(node))
+            | Self::CallExpr(CallExpr(node))
+            | Self::IfExpr(IfExpr(node))
+            | Self::Literal(Literal(node))
+            | Self::ParenExpr(ParenExpr(node))
+            | Self::UnaryExpr(UnaryExpr(node))
+            | Self::VariableRef(VariableRef(node)) => node,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AssignExpr(SyntaxNode);
+
+impl AssignExpr {
+    pub fn target(&self) -> Option<Expr> {
+        self.0.children().find_map(Expr::cast)
+    }
+
+    pub fn value(&self) -> Option<Expr> {
+        self.0.children().filter_map(Expr::cast).nth(1)
+    }
+
+    /// Returns either `=` or one of the compound assignment operators, such as `+=`.
+    pub fn op(&self) -> Option<SyntaxToken> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| {
+                matches!(
+                    token.kind(),
+                    SyntaxKind::Equals
+                        | SyntaxKind::PlusEquals
+                        | SyntaxKind::MinusEquals
+                        | SyntaxKind::StarEquals
+                        | SyntaxKind::SlashEquals,
+                )
+            })
+    }
 }
 
 #[derive(Debug)]
@@ -95,12 +296,122 @@ impl BinaryExpr {
             .find(|token| {
                 matches!(
                     token.kind(),
-                    SyntaxKind::Plus | SyntaxKind::Minus | SyntaxKind::Star | SyntaxKind::Slash,
+                    SyntaxKind::Plus
+                        | SyntaxKind::Minus
+                        | SyntaxKind::Star
+                        | SyntaxKind::Slash
+                        | SyntaxKind::Percent
+                        | SyntaxKind::StarStar
+                        | SyntaxKind::Amp
+                        | SyntaxKind::Pipe
+                        | SyntaxKind::Caret
+                        | SyntaxKind::LessLess
+                        | SyntaxKind::GreaterGreater
+                        | SyntaxKind::EqualsEquals
+                        | SyntaxKind::BangEquals
+                        | SyntaxKind::Less
+                        | SyntaxKind::LessEquals
+                        | SyntaxKind::Greater
+                        | SyntaxKind::GreaterEquals
+                        | SyntaxKind::AmpAmp
+                        | SyntaxKind::PipePipe,
                 )
             })
     }
 }
 
+#[derive(Debug)]
+pub struct BlockExpr(SyntaxNode);
+
+impl BlockExpr {
+    /// Returns every statement in the block, including the expression it may end with.
+    pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+        self.0.children().filter_map(Stmt::cast)
+    }
+}
+
+#[derive(Debug)]
+pub struct CallExpr(SyntaxNode);
+
+impl CallExpr {
+    pub fn callee(&self) -> Option<VariableRef> {
+        self.0
+            .children()
+            .find(|node| node.kind() == SyntaxKind::VariableRef)
+            .map(VariableRef)
+    }
+
+    pub fn arg_list(&self) -> Option<ArgList> {
+        self.0.children().find_map(ArgList::cast)
+    }
+}
+
+#[derive(Debug)]
+pub struct ArgList(SyntaxNode);
+
+impl ArgList {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if node.kind() == SyntaxKind::ArgList {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn args(&self) -> impl Iterator<Item = Expr> {
+        self.0.children().filter_map(Expr::cast)
+    }
+}
+
+#[derive(Debug)]
+pub struct IfExpr(SyntaxNode);
+
+impl IfExpr {
+    pub fn condition(&self) -> Option<Expr> {
+        self.condition_and_then_branch().0
+    }
+
+    pub fn then_branch(&self) -> Option<Expr> {
+        self.condition_and_then_branch().1
+    }
+
+    pub fn else_branch(&self) -> Option<Expr> {
+        self.0
+            .children_with_tokens()
+            .skip_while(|element| element.kind() != SyntaxKind::ElseKw)
+            .filter_map(SyntaxElement::into_node)
+            .find_map(Expr::cast)
+    }
+
+    fn condition_and_then_branch(&self) -> (Option<Expr>, Option<Expr>) {
+        condition_and_block(self.exprs_before_else())
+    }
+
+    fn exprs_before_else(&self) -> impl Iterator<Item = Expr> {
+        self.0
+            .children_with_tokens()
+            .take_while(|element| element.kind() != SyntaxKind::ElseKw)
+            .filter_map(SyntaxElement::into_node)
+            .filter_map(Expr::cast)
+    }
+}
+
+/// Splits the expressions of an `if` (up to its ‘else’) or a `while` into the condition and the
+/// block that follows it. The block is the last expression and the condition is whatever comes
+/// before it. Going by position alone would take the block in `if { 1 }` for the condition, even
+/// though it’s the condition that’s missing.
+fn condition_and_block(exprs: impl Iterator<Item = Expr>) -> (Option<Expr>, Option<Expr>) {
+    let mut exprs: Vec<_> = exprs.collect();
+
+    let block = match exprs.last() {
+        Some(Expr::BlockExpr(_)) => exprs.pop(),
+        _ => None,
+    };
+    let condition = exprs.into_iter().next();
+
+    (condition, block)
+}
+
 #[derive(Debug)]
 pub struct Literal(SyntaxNode);
 
@@ -113,11 +424,39 @@ impl Literal {
         }
     }
 
-    pub fn parse(&self) -> Option<u64> {
-        self.0.first_token().unwrap().text().parse().ok()
+    pub fn kind(&self) -> LiteralKind {
+        let token = self.0.first_token().unwrap();
+
+        match token.kind() {
+            SyntaxKind::Number => {
+                let text = token.text();
+                let digits_end = text
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(text.len());
+
+                LiteralKind::Number {
+                    // The lexer only produces number tokens that start with at least one digit.
+                    value: text[..digits_end].parse().unwrap(),
+                    suffix: IntTy::from_name(&text[digits_end..]),
+                }
+            }
+            SyntaxKind::TrueKw => LiteralKind::Bool(true),
+            SyntaxKind::FalseKw => LiteralKind::Bool(false),
+            _ => unreachable!(),
+        }
     }
 }
 
+#[derive(Debug, PartialEq)]
+pub enum LiteralKind {
+    Number {
+        value: BigInt,
+        /// the type given by the literal’s suffix, such as the `u8` in `5u8`
+        suffix: Option<IntTy>,
+    },
+    Bool(bool),
+}
+
 #[derive(Debug)]
 pub struct ParenExpr(SyntaxNode);
 
@@ -139,7 +478,12 @@ impl UnaryExpr {
         self.0
             .children_with_tokens()
             .filter_map(SyntaxElement::into_token)
-            .find(|token| token.kind() == SyntaxKind::Minus)
+            .find(|token| {
+                matches!(
+                    token.kind(),
+                    SyntaxKind::Minus | SyntaxKind::Bang | SyntaxKind::Tilde,
+                )
+            })
     }
 }
 
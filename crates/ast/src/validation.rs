@@ -1,64 +1,164 @@
-use crate::Literal;
+use crate::{trimmed_range, AssignExpr, Expr, IntTy, Literal, LiteralKind};
+use diagnostics::Diagnostic;
 use std::fmt;
-use syntax::SyntaxNode;
+use syntax::{SyntaxKind, SyntaxNode};
 use text_size::TextRange;
 
 #[derive(Debug, PartialEq)]
-pub struct ValidationError {
+struct ValidationError {
     kind: ValidationErrorKind,
     range: TextRange,
 }
 
-impl fmt::Display for ValidationError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "error at {}..{}: {}",
-            u32::from(self.range.start()),
-            u32::from(self.range.end()),
-            self.kind,
-        )
+impl ValidationError {
+    fn into_diagnostic(self) -> Diagnostic {
+        let diagnostic = Diagnostic::error(self.kind.code(), self.kind.to_string(), self.range);
+
+        match self.kind {
+            ValidationErrorKind::LiteralOutOfRange { ty } => diagnostic
+                .with_label(format!("this number doesn’t fit in ‘{}’", ty))
+                .with_note(format!("‘{}’ ranges from {} to {}", ty, ty.min(), ty.max())),
+            ValidationErrorKind::BreakOutsideLoop => {
+                diagnostic.with_label("cannot ‘break’ outside of a loop")
+            }
+            ValidationErrorKind::ContinueOutsideLoop => {
+                diagnostic.with_label("cannot ‘continue’ outside of a loop")
+            }
+            ValidationErrorKind::InvalidAssignmentTarget => diagnostic
+                .with_label("cannot assign to this expression")
+                .with_note("only variables can be assigned to"),
+        }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 enum ValidationErrorKind {
-    NumberLiteralTooLarge,
+    LiteralOutOfRange { ty: IntTy },
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
+    InvalidAssignmentTarget,
+}
+
+impl ValidationErrorKind {
+    fn code(self) -> &'static str {
+        match self {
+            Self::LiteralOutOfRange { .. } => "E0002",
+            Self::BreakOutsideLoop | Self::ContinueOutsideLoop => "E0003",
+            Self::InvalidAssignmentTarget => "E0005",
+        }
+    }
 }
 
 impl fmt::Display for ValidationErrorKind {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::NumberLiteralTooLarge => write!(
-                f,
-                "number literal is larger than an integer’s maximum value, {}",
-                u64::MAX,
-            ),
+            Self::LiteralOutOfRange { ty } => write!(f, "literal out of range for ‘{}’", ty),
+            Self::BreakOutsideLoop => write!(f, "‘break’ outside of a loop"),
+            Self::ContinueOutsideLoop => write!(f, "‘continue’ outside of a loop"),
+            Self::InvalidAssignmentTarget => write!(f, "invalid left-hand side of assignment"),
         }
     }
 }
 
-pub fn validate(node: &SyntaxNode) -> Vec<ValidationError> {
+pub fn validate(node: &SyntaxNode) -> Vec<Diagnostic> {
     let mut errors = Vec::new();
 
     for node in node.descendants() {
-        if let Some(literal) = Literal::cast(node) {
-            validate_literal(literal, &mut errors)
+        match node.kind() {
+            SyntaxKind::Break => {
+                validate_loop_control(node, ValidationErrorKind::BreakOutsideLoop, &mut errors)
+            }
+            SyntaxKind::Continue => {
+                validate_loop_control(node, ValidationErrorKind::ContinueOutsideLoop, &mut errors)
+            }
+            SyntaxKind::AssignExpr => validate_assign(AssignExpr(node), &mut errors),
+            _ => {
+                if let Some(literal) = Literal::cast(node) {
+                    validate_literal(literal, &mut errors)
+                }
+            }
         }
     }
 
     errors
+        .into_iter()
+        .map(ValidationError::into_diagnostic)
+        .collect()
 }
 
+/// Reports number literals with a suffix that don’t fit in their type. Literals without a suffix
+/// are checked by the type checker once their type has been inferred.
 fn validate_literal(literal: Literal, errors: &mut Vec<ValidationError>) {
-    if literal.parse().is_none() {
+    let (value, ty) = match literal.kind() {
+        LiteralKind::Number {
+            value,
+            suffix: Some(ty),
+        } => (value, ty),
+        LiteralKind::Number { suffix: None, .. } | LiteralKind::Bool(_) => return,
+    };
+
+    if ty.literal_value(&value, is_negated(&literal)).is_none() {
         errors.push(ValidationError {
-            kind: ValidationErrorKind::NumberLiteralTooLarge,
+            kind: ValidationErrorKind::LiteralOutOfRange { ty },
             range: literal.0.first_token().unwrap().text_range(),
         });
     }
 }
 
+/// Returns whether `literal` is the operand of a `-`, ignoring any parentheses around it.
+fn is_negated(literal: &Literal) -> bool {
+    literal
+        .0
+        .ancestors()
+        .skip(1)
+        .find(|ancestor| ancestor.kind() != SyntaxKind::ParenExpr)
+        .and_then(|ancestor| match Expr::cast(ancestor)? {
+            Expr::UnaryExpr(unary) => unary.op(),
+            _ => None,
+        })
+        .is_some_and(|op| op.kind() == SyntaxKind::Minus)
+}
+
+/// Reports `break` and `continue` statements that aren’t inside the body of a loop. Loops outside
+/// of the function a statement is in don’t count, since the function could be called from
+/// anywhere.
+fn validate_loop_control(
+    node: SyntaxNode,
+    kind: ValidationErrorKind,
+    errors: &mut Vec<ValidationError>,
+) {
+    let in_loop_body = node
+        .ancestors()
+        .take_while(|ancestor| ancestor.kind() != SyntaxKind::FnDef)
+        .any(|ancestor| {
+            // A loop’s body comes after its condition, so this excludes blocks in the condition.
+            ancestor.kind() == SyntaxKind::BlockExpr
+                && ancestor.parent().is_some_and(|parent| {
+                    parent.kind() == SyntaxKind::WhileLoop
+                        && parent.children().next().as_ref() != Some(&ancestor)
+                })
+        });
+
+    if !in_loop_body {
+        errors.push(ValidationError {
+            kind,
+            range: node.first_token().unwrap().text_range(),
+        });
+    }
+}
+
+/// Reports assignments to anything other than a variable. Whether the variable can be assigned
+/// to is checked during name resolution.
+fn validate_assign(assign: AssignExpr, errors: &mut Vec<ValidationError>) {
+    match assign.target() {
+        Some(Expr::VariableRef(_)) | None => {}
+        Some(target) => errors.push(ValidationError {
+            kind: ValidationErrorKind::InvalidAssignmentTarget,
+            range: trimmed_range(target.syntax()),
+        }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,21 +177,182 @@ mod tests {
                     TextRange::new(start, end)
                 },
             })
+            .map(ValidationError::into_diagnostic)
             .collect();
 
         assert_eq!(validate(&parse.syntax()), expected_errors);
     }
 
+    fn check_diagnostic(input: &str, expected_diagnostic: Diagnostic) {
+        let parse = parser::parse(input);
+        assert_eq!(validate(&parse.syntax()), vec![expected_diagnostic]);
+    }
+
     #[test]
     fn validate_ok_literal() {
         check("123", &[]);
     }
 
     #[test]
-    fn validate_too_large_literal() {
+    fn validate_literal_larger_than_every_fixed_width_type() {
+        check(
+            "123456789012345678901234567890123456789012345678901234567890",
+            &[],
+        );
+    }
+
+    #[test]
+    fn validate_boolean_literal() {
+        check("true", &[]);
+    }
+
+    #[test]
+    fn validate_break_in_loop() {
+        check("while true { break }", &[]);
+    }
+
+    #[test]
+    fn validate_continue_in_nested_block_in_loop() {
+        check("while true { if false { continue } }", &[]);
+    }
+
+    #[test]
+    fn validate_break_outside_loop() {
+        check(
+            "let a = 1\nbreak",
+            &[(ValidationErrorKind::BreakOutsideLoop, (10..15))],
+        );
+    }
+
+    #[test]
+    fn validate_continue_outside_loop() {
+        check(
+            "{ continue }",
+            &[(ValidationErrorKind::ContinueOutsideLoop, (2..10))],
+        );
+    }
+
+    #[test]
+    fn validate_break_in_loop_condition() {
         check(
-            "99999999999999999999",
-            &[(ValidationErrorKind::NumberLiteralTooLarge, (0..20))],
+            "while { break } {}",
+            &[(ValidationErrorKind::BreakOutsideLoop, (8..13))],
+        );
+    }
+
+    #[test]
+    fn validate_break_in_fn_inside_loop() {
+        check(
+            "while true { fn f() { break } }",
+            &[(ValidationErrorKind::BreakOutsideLoop, (22..27))],
+        );
+    }
+
+    #[test]
+    fn break_outside_loop_diagnostic() {
+        check_diagnostic(
+            "break",
+            Diagnostic::error(
+                "E0003",
+                "‘break’ outside of a loop",
+                TextRange::new(0.into(), 5.into()),
+            )
+            .with_label("cannot ‘break’ outside of a loop"),
+        );
+    }
+
+    #[test]
+    fn validate_assign_to_mutable_variable() {
+        check("let mut a = 1\na = 2\na += 3", &[]);
+    }
+
+    #[test]
+    fn validate_invalid_assignment_target() {
+        check(
+            "1 + 2 = 3",
+            &[(ValidationErrorKind::InvalidAssignmentTarget, (0..5))],
+        );
+    }
+
+    #[test]
+    fn invalid_assignment_target_diagnostic() {
+        check_diagnostic(
+            "(a) = 1",
+            Diagnostic::error(
+                "E0005",
+                "invalid left-hand side of assignment",
+                TextRange::new(0.into(), 3.into()),
+            )
+            .with_label("cannot assign to this expression")
+            .with_note("only variables can be assigned to"),
+        );
+    }
+
+    #[test]
+    fn validate_literal_with_suffix_larger_than_i128() {
+        check(
+            "123456789012345678901234567890123456789012345678901234567890u64",
+            &[(
+                ValidationErrorKind::LiteralOutOfRange { ty: IntTy::U64 },
+                (0..63),
+            )],
+        );
+    }
+
+    #[test]
+    fn validate_literals_with_suffixes() {
+        check("255u8", &[]);
+        check("127i8", &[]);
+        check("-128i8", &[]);
+        check("18446744073709551615u64", &[]);
+    }
+
+    #[test]
+    fn validate_out_of_range_literals_with_suffixes() {
+        check(
+            "256u8",
+            &[(
+                ValidationErrorKind::LiteralOutOfRange { ty: IntTy::U8 },
+                (0..5),
+            )],
+        );
+        check(
+            "128i8",
+            &[(
+                ValidationErrorKind::LiteralOutOfRange { ty: IntTy::I8 },
+                (0..5),
+            )],
+        );
+        check(
+            "-129i8",
+            &[(
+                ValidationErrorKind::LiteralOutOfRange { ty: IntTy::I8 },
+                (1..6),
+            )],
+        );
+    }
+
+    #[test]
+    fn validate_negated_literal_in_parentheses() {
+        check("-(128i8)", &[]);
+    }
+
+    #[test]
+    fn validate_negation_of_unsigned_literal() {
+        check("-5u8", &[]);
+    }
+
+    #[test]
+    fn literal_out_of_range_diagnostic() {
+        check_diagnostic(
+            "let a = 40000i16",
+            Diagnostic::error(
+                "E0002",
+                "literal out of range for ‘i16’",
+                TextRange::new(8.into(), 16.into()),
+            )
+            .with_label("this number doesn’t fit in ‘i16’")
+            .with_note("‘i16’ ranges from -32768 to 32767"),
         );
     }
 }
@@ -0,0 +1,336 @@
+mod magnitude;
+mod ops;
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// An integer that can be arbitrarily large, limited only by memory.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    /// never true for zero, so that every integer has only one representation
+    negative: bool,
+    /// the absolute value, as digits in base 2^32 with the least significant digit first and no
+    /// trailing zeros
+    magnitude: Vec<u32>,
+}
+
+impl BigInt {
+    fn from_parts(negative: bool, magnitude: Vec<u32>) -> Self {
+        let magnitude = magnitude::normalize(magnitude);
+
+        Self {
+            negative: negative && !magnitude.is_empty(),
+            magnitude,
+        }
+    }
+
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The number of bits needed to write the absolute value in binary, which is zero for zero.
+    pub fn bits(&self) -> u64 {
+        match self.magnitude.last() {
+            Some(last) => self.magnitude.len() as u64 * 32 - u64::from(last.leading_zeros()),
+            None => 0,
+        }
+    }
+
+    /// Returns `None` if the integer is outside of `i128`’s range.
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.magnitude.len() > 4 {
+            return None;
+        }
+
+        let magnitude = self
+            .magnitude
+            .iter()
+            .rev()
+            .fold(0_u128, |magnitude, digit| {
+                magnitude << 32 | u128::from(*digit)
+            });
+
+        if self.negative {
+            0_i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    /// Divides by `other`, rounding towards zero, and returns the quotient along with the
+    /// remainder, which has the same sign as `self`. This matches the behaviour of Rust’s integer
+    /// types.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    pub fn div_rem(&self, other: &Self) -> (Self, Self) {
+        let (quotient, remainder) = magnitude::div_rem(&self.magnitude, &other.magnitude);
+
+        (
+            Self::from_parts(self.negative != other.negative, quotient),
+            Self::from_parts(self.negative, remainder),
+        )
+    }
+
+    pub fn pow(&self, mut exponent: u32) -> Self {
+        let mut result = Self::from(1);
+        let mut square = self.clone();
+
+        while exponent > 0 {
+            if exponent % 2 == 1 {
+                result = &result * &square;
+            }
+
+            exponent /= 2;
+
+            if exponent > 0 {
+                square = &square * &square;
+            }
+        }
+
+        result
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for BigInt {
+                fn from(n: $ty) -> Self {
+                    let n = n as u128;
+                    let digits = vec![n as u32, (n >> 32) as u32, (n >> 64) as u32, (n >> 96) as u32];
+
+                    Self::from_parts(false, digits)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_signed {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for BigInt {
+                fn from(n: $ty) -> Self {
+                    let BigInt { magnitude, .. } = Self::from((n as i128).unsigned_abs());
+                    Self::from_parts(n < 0, magnitude)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => magnitude::cmp(&self.magnitude, &other.magnitude),
+            (true, true) => magnitude::cmp(&other.magnitude, &self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The largest power of ten that fits in a digit, which numbers are converted to and from decimal
+/// in chunks of.
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut chunks = Vec::new();
+        let mut rest = self.magnitude.clone();
+
+        while !rest.is_empty() {
+            let (quotient, chunk) = magnitude::div_rem_digit(&rest, DECIMAL_CHUNK);
+            chunks.push(chunk);
+            rest = quotient;
+        }
+
+        let mut digits = match chunks.pop() {
+            Some(most_significant) => most_significant.to_string(),
+            None => "0".to_string(),
+        };
+
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:01$}", chunk, DECIMAL_CHUNK_DIGITS));
+        }
+
+        f.pad_integral(!self.negative, "", &digits)
+    }
+}
+
+/// The error returned when parsing a string that isn’t a decimal integer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseBigIntError;
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid decimal integer")
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    /// Parses decimal digits, optionally preceded by a `-`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+
+        if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(ParseBigIntError);
+        }
+
+        let mut magnitude = Vec::new();
+
+        // Chunks are taken from the end so that only the most significant one can be shorter.
+        for chunk in digits.as_bytes().rchunks(DECIMAL_CHUNK_DIGITS).rev() {
+            let chunk = chunk
+                .iter()
+                .fold(0, |chunk, digit| chunk * 10 + u32::from(digit - b'0'));
+
+            magnitude = magnitude::mul(&magnitude, &[DECIMAL_CHUNK]);
+            magnitude = magnitude::add(&magnitude, &magnitude::normalize(vec![chunk]));
+        }
+
+        Ok(Self::from_parts(negative, magnitude))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> BigInt {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn display_small_numbers() {
+        assert_eq!(BigInt::zero().to_string(), "0");
+        assert_eq!(BigInt::from(42).to_string(), "42");
+        assert_eq!(BigInt::from(-7).to_string(), "-7");
+        assert_eq!(BigInt::from(i128::MIN).to_string(), i128::MIN.to_string());
+    }
+
+    #[test]
+    fn parse_and_display_round_trip() {
+        for s in &[
+            "0",
+            "1000000000",
+            "-999999999",
+            "340282366920938463463374607431768211456",
+            "-1000000000000000000000000000000000000000000000000000000001",
+        ] {
+            assert_eq!(parse(s).to_string(), *s);
+        }
+    }
+
+    #[test]
+    fn parse_leading_zeros() {
+        assert_eq!(parse("0000000000000012"), BigInt::from(12));
+        assert_eq!(parse("-0"), BigInt::zero());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        for s in &["", "-", "12a", "+5", " 1"] {
+            assert_eq!(s.parse::<BigInt>(), Err(ParseBigIntError));
+        }
+    }
+
+    #[test]
+    fn display_respects_width() {
+        assert_eq!(format!("{:>5}", BigInt::from(-12)), "  -12");
+        assert_eq!(format!("{:+}", BigInt::from(3)), "+3");
+    }
+
+    #[test]
+    fn to_i128() {
+        assert_eq!(BigInt::from(i128::MAX).to_i128(), Some(i128::MAX));
+        assert_eq!(BigInt::from(i128::MIN).to_i128(), Some(i128::MIN));
+        assert_eq!(
+            (&BigInt::from(i128::MAX) + &BigInt::from(1)).to_i128(),
+            None
+        );
+        assert_eq!(
+            (&BigInt::from(i128::MIN) - &BigInt::from(1)).to_i128(),
+            None
+        );
+        assert_eq!(parse("1").pow(200).to_i128(), Some(1));
+    }
+
+    #[test]
+    fn bits() {
+        assert_eq!(BigInt::zero().bits(), 0);
+        assert_eq!(BigInt::from(1).bits(), 1);
+        assert_eq!(BigInt::from(-255).bits(), 8);
+        assert_eq!(BigInt::from(1_u128 << 100).bits(), 101);
+    }
+
+    #[test]
+    fn ordering() {
+        let mut numbers = vec![
+            parse("100000000000000000000"),
+            BigInt::from(-3),
+            BigInt::zero(),
+            parse("-100000000000000000000"),
+            BigInt::from(5),
+        ];
+        numbers.sort();
+
+        assert_eq!(
+            numbers,
+            vec![
+                parse("-100000000000000000000"),
+                BigInt::from(-3),
+                BigInt::zero(),
+                BigInt::from(5),
+                parse("100000000000000000000"),
+            ],
+        );
+    }
+
+    #[test]
+    fn div_rem_rounds_towards_zero() {
+        for &(a, b) in &[(7, 2), (-7, 2), (7, -2), (-7, -2), (6, 3), (1, 10)] {
+            assert_eq!(
+                BigInt::from(a).div_rem(&BigInt::from(b)),
+                (BigInt::from(a / b), BigInt::from(a % b)),
+            );
+        }
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(
+            BigInt::from(2).pow(128).to_string(),
+            "340282366920938463463374607431768211456",
+        );
+        assert_eq!(BigInt::from(-3).pow(3), BigInt::from(-27));
+        assert_eq!(BigInt::zero().pow(0), BigInt::from(1));
+    }
+}
@@ -0,0 +1,302 @@
+//! Arithmetic on the magnitudes of integers, which are stored as digits in base 2^32 with the
+//! least significant digit first. Every function expects its inputs to have no trailing zero
+//! digits and returns results that don’t either.
+
+use std::cmp::Ordering;
+
+const DIGIT_BITS: usize = 32;
+
+/// Removes the most significant digits that are zero.
+pub(crate) fn normalize(mut digits: Vec<u32>) -> Vec<u32> {
+    while digits.last() == Some(&0) {
+        digits.pop();
+    }
+
+    digits
+}
+
+pub(crate) fn cmp(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+pub(crate) fn add(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (longer, shorter) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = Vec::with_capacity(longer.len() + 1);
+    let mut carry = 0;
+
+    for (idx, digit) in longer.iter().enumerate() {
+        let total = u64::from(*digit) + u64::from(shorter.get(idx).copied().unwrap_or(0)) + carry;
+        sum.push(total as u32);
+        carry = total >> DIGIT_BITS;
+    }
+
+    if carry != 0 {
+        sum.push(carry as u32);
+    }
+
+    sum
+}
+
+/// Subtracts `b` from `a`, which must be at least as large.
+pub(crate) fn sub(a: &[u32], b: &[u32]) -> Vec<u32> {
+    debug_assert!(cmp(a, b) != Ordering::Less);
+
+    let mut difference = Vec::with_capacity(a.len());
+    let mut borrow = 0;
+
+    for (idx, digit) in a.iter().enumerate() {
+        let total = i64::from(*digit) - i64::from(b.get(idx).copied().unwrap_or(0)) - borrow;
+        difference.push(total as u32);
+        borrow = if total < 0 { 1 } else { 0 };
+    }
+
+    normalize(difference)
+}
+
+pub(crate) fn mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let mut product = vec![0; a.len() + b.len()];
+
+    for (i, a_digit) in a.iter().enumerate() {
+        let mut carry = 0;
+
+        for (j, b_digit) in b.iter().enumerate() {
+            // This can’t overflow, since (2^32 - 1)^2 + 2 * (2^32 - 1) = 2^64 - 1.
+            let total =
+                u64::from(*a_digit) * u64::from(*b_digit) + u64::from(product[i + j]) + carry;
+            product[i + j] = total as u32;
+            carry = total >> DIGIT_BITS;
+        }
+
+        product[i + b.len()] = carry as u32;
+    }
+
+    normalize(product)
+}
+
+/// Divides `a` by `b`, which mustn’t be zero, returning the quotient and the remainder.
+pub(crate) fn div_rem(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    assert!(!b.is_empty(), "attempt to divide by zero");
+
+    if cmp(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+
+    if let [divisor] = b {
+        let (quotient, remainder) = div_rem_digit(a, *divisor);
+        return (quotient, normalize(vec![remainder]));
+    }
+
+    // This is algorithm D from section 4.3.1 of The Art of Computer Programming. Both numbers
+    // are shifted so that the divisor’s most significant digit has its top bit set, which makes
+    // each estimate of a quotient digit at most two more than the real digit.
+    let shift = b.last().unwrap().leading_zeros() as usize;
+    let divisor = shl(b, shift);
+    let mut remainder = shl(a, shift);
+    remainder.resize(a.len() + 1, 0);
+
+    let n = divisor.len();
+    let mut quotient = vec![0; a.len() - n + 1];
+    let base = 1 << DIGIT_BITS;
+
+    for j in (0..quotient.len()).rev() {
+        let top = u64::from(remainder[j + n]) << DIGIT_BITS | u64::from(remainder[j + n - 1]);
+        let mut estimate = top / u64::from(divisor[n - 1]);
+        let mut estimate_remainder = top % u64::from(divisor[n - 1]);
+
+        while estimate >= base
+            || estimate * u64::from(divisor[n - 2])
+                > (estimate_remainder << DIGIT_BITS | u64::from(remainder[j + n - 2]))
+        {
+            estimate -= 1;
+            estimate_remainder += u64::from(divisor[n - 1]);
+
+            if estimate_remainder >= base {
+                break;
+            }
+        }
+
+        // Subtract the estimate times the divisor from the current digits of the remainder.
+        let mut borrow = 0;
+
+        for i in 0..n {
+            let product = estimate * u64::from(divisor[i]);
+            let total = i64::from(remainder[i + j]) - borrow - (product & (base - 1)) as i64;
+            remainder[i + j] = total as u32;
+            borrow = (product >> DIGIT_BITS) as i64 - (total >> DIGIT_BITS);
+        }
+
+        let total = i64::from(remainder[j + n]) - borrow;
+        remainder[j + n] = total as u32;
+
+        // The estimate was one too large, so add the divisor back.
+        if total < 0 {
+            estimate -= 1;
+            let mut carry = 0;
+
+            for i in 0..n {
+                let total = u64::from(remainder[i + j]) + u64::from(divisor[i]) + carry;
+                remainder[i + j] = total as u32;
+                carry = total >> DIGIT_BITS;
+            }
+
+            remainder[j + n] = remainder[j + n].wrapping_add(carry as u32);
+        }
+
+        quotient[j] = estimate as u32;
+    }
+
+    remainder.truncate(n);
+
+    (normalize(quotient), shr(&normalize(remainder), shift))
+}
+
+/// Divides `a` by a single digit, which mustn’t be zero.
+pub(crate) fn div_rem_digit(a: &[u32], b: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0; a.len()];
+    let mut remainder = 0;
+
+    for (idx, digit) in a.iter().enumerate().rev() {
+        let total = remainder << DIGIT_BITS | u64::from(*digit);
+        quotient[idx] = (total / u64::from(b)) as u32;
+        remainder = total % u64::from(b);
+    }
+
+    (normalize(quotient), remainder as u32)
+}
+
+pub(crate) fn shl(a: &[u32], amount: usize) -> Vec<u32> {
+    if a.is_empty() {
+        return Vec::new();
+    }
+
+    let (digits, bits) = (amount / DIGIT_BITS, amount % DIGIT_BITS);
+    let mut shifted = vec![0; digits];
+    shifted.reserve(a.len() + 1);
+
+    if bits == 0 {
+        shifted.extend_from_slice(a);
+    } else {
+        let mut carry = 0;
+
+        for digit in a {
+            shifted.push(digit << bits | carry);
+            carry = digit >> (DIGIT_BITS - bits);
+        }
+
+        shifted.push(carry);
+    }
+
+    normalize(shifted)
+}
+
+pub(crate) fn shr(a: &[u32], amount: usize) -> Vec<u32> {
+    let (digits, bits) = (amount / DIGIT_BITS, amount % DIGIT_BITS);
+
+    if digits >= a.len() {
+        return Vec::new();
+    }
+
+    let a = &a[digits..];
+
+    if bits == 0 {
+        return a.to_vec();
+    }
+
+    let shifted = a
+        .iter()
+        .enumerate()
+        .map(|(idx, digit)| {
+            let next = a.get(idx + 1).copied().unwrap_or(0);
+            digit >> bits | next << (DIGIT_BITS - bits)
+        })
+        .collect();
+
+    normalize(shifted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(n: u128) -> Vec<u32> {
+        normalize(vec![
+            n as u32,
+            (n >> 32) as u32,
+            (n >> 64) as u32,
+            (n >> 96) as u32,
+        ])
+    }
+
+    const SAMPLES: &[u128] = &[
+        0,
+        1,
+        7,
+        0xffff_ffff,
+        0x1_0000_0000,
+        0xdead_beef_cafe_f00d,
+        0xffff_ffff_ffff_ffff,
+        0x8000_0000_0000_0000_0000_0001,
+        0x1234_5678_9abc_def0_1234_5678_9abc_def0,
+        0x0fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff,
+    ];
+
+    #[test]
+    fn arithmetic_matches_u128() {
+        for &a in SAMPLES {
+            for &b in SAMPLES {
+                let (x, y) = (digits(a), digits(b));
+
+                assert_eq!(cmp(&x, &y), a.cmp(&b));
+
+                if let Some(sum) = a.checked_add(b) {
+                    assert_eq!(add(&x, &y), digits(sum));
+                }
+
+                if a >= b {
+                    assert_eq!(sub(&x, &y), digits(a - b));
+                }
+
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(mul(&x, &y), digits(product));
+                }
+
+                if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+                    assert_eq!(div_rem(&x, &y), (digits(quotient), digits(remainder)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn shifts_match_u128() {
+        for &a in &SAMPLES[..7] {
+            for &amount in &[0, 1, 13, 32, 33, 63] {
+                assert_eq!(shl(&digits(a), amount), digits(a << amount));
+                assert_eq!(shr(&digits(a), amount), digits(a >> amount));
+            }
+        }
+
+        assert_eq!(shr(&digits(u128::MAX), 200), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn div_rem_that_adds_divisor_back() {
+        // The first estimated quotient digit here is one too large, which is only discovered after
+        // subtracting.
+        let (a, b) = (
+            0xffff_ffff_0000_0002_8000_0001_0000_0000,
+            0x8000_0000_0000_0001_8000_0001,
+        );
+
+        assert_eq!(
+            div_rem(&digits(a), &digits(b)),
+            (digits(a / b), digits(a % b))
+        );
+    }
+}
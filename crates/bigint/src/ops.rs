@@ -0,0 +1,260 @@
+use crate::{magnitude, BigInt};
+use std::cmp::Ordering;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
+
+/// Adds two integers given as their signs and magnitudes.
+fn add(a_negative: bool, a: &[u32], b_negative: bool, b: &[u32]) -> BigInt {
+    if a_negative == b_negative {
+        return BigInt::from_parts(a_negative, magnitude::add(a, b));
+    }
+
+    // The result has the sign of whichever operand has the larger magnitude.
+    match magnitude::cmp(a, b) {
+        Ordering::Less => BigInt::from_parts(b_negative, magnitude::sub(b, a)),
+        Ordering::Equal | Ordering::Greater => BigInt::from_parts(a_negative, magnitude::sub(a, b)),
+    }
+}
+
+impl Add for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: Self) -> BigInt {
+        add(
+            self.negative,
+            &self.magnitude,
+            other.negative,
+            &other.magnitude,
+        )
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: Self) -> BigInt {
+        add(
+            self.negative,
+            &self.magnitude,
+            !other.negative,
+            &other.magnitude,
+        )
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: Self) -> BigInt {
+        BigInt::from_parts(
+            self.negative != other.negative,
+            magnitude::mul(&self.magnitude, &other.magnitude),
+        )
+    }
+}
+
+impl Div for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: Self) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: Self) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+/// Bitwise operators act as if integers were stored in two’s complement with infinitely many
+/// copies of the sign bit, so `!n` is `-n - 1`.
+impl Not for &BigInt {
+    type Output = BigInt;
+
+    fn not(self) -> BigInt {
+        &-self - &BigInt::from(1)
+    }
+}
+
+/// Returns the lowest `len` digits of the two’s complement representation of `n`.
+fn to_twos_complement(n: &BigInt, len: usize) -> Vec<u32> {
+    let mut digits = n.magnitude.clone();
+    digits.resize(len, 0);
+
+    if n.negative {
+        negate_twos_complement(&mut digits);
+    }
+
+    digits
+}
+
+/// Interprets `digits` as a two’s complement integer, so it is negative if its top bit is set.
+fn from_twos_complement(mut digits: Vec<u32>) -> BigInt {
+    let negative = digits.last().is_some_and(|digit| digit >> 31 == 1);
+
+    if negative {
+        negate_twos_complement(&mut digits);
+    }
+
+    BigInt::from_parts(negative, digits)
+}
+
+/// Applies `op` to each pair of digits in the two’s complement representations of `a` and `b`.
+fn bitwise(a: &BigInt, b: &BigInt, op: impl Fn(u32, u32) -> u32) -> BigInt {
+    // The extra digit leaves room for the sign bit.
+    let len = a.magnitude.len().max(b.magnitude.len()) + 1;
+    let (a, b) = (to_twos_complement(a, len), to_twos_complement(b, len));
+
+    from_twos_complement(a.iter().zip(&b).map(|(a, b)| op(*a, *b)).collect())
+}
+
+fn negate_twos_complement(digits: &mut [u32]) {
+    let mut carry = true;
+
+    for digit in digits {
+        let (sum, overflowed) = (!*digit).overflowing_add(u32::from(carry));
+        *digit = sum;
+        carry = overflowed;
+    }
+}
+
+macro_rules! impl_bitwise_op {
+    ($trait:ident, $method:ident, $op:tt) => {
+        impl $trait for &BigInt {
+            type Output = BigInt;
+
+            fn $method(self, other: Self) -> BigInt {
+                bitwise(self, other, |a, b| a $op b)
+            }
+        }
+    };
+}
+
+impl_bitwise_op!(BitAnd, bitand, &);
+impl_bitwise_op!(BitOr, bitor, |);
+impl_bitwise_op!(BitXor, bitxor, ^);
+
+impl Shl<u32> for &BigInt {
+    type Output = BigInt;
+
+    fn shl(self, amount: u32) -> BigInt {
+        BigInt::from_parts(
+            self.negative,
+            magnitude::shl(&self.magnitude, amount as usize),
+        )
+    }
+}
+
+/// Shifting right divides by a power of two, rounding towards negative infinity.
+impl Shr<u32> for &BigInt {
+    type Output = BigInt;
+
+    fn shr(self, amount: u32) -> BigInt {
+        if !self.negative {
+            return BigInt::from_parts(false, magnitude::shr(&self.magnitude, amount as usize));
+        }
+
+        // -n >> amount is -(((n - 1) >> amount) + 1)
+        let one = [1];
+        let shifted = magnitude::shr(&magnitude::sub(&self.magnitude, &one), amount as usize);
+
+        BigInt::from_parts(true, magnitude::add(&shifted, &one))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: &[i128] = &[
+        0,
+        1,
+        -1,
+        5,
+        -6,
+        0xffff_ffff,
+        -0x1_0000_0000,
+        i64::MAX as i128,
+        i64::MIN as i128,
+        0x7654_3210_fedc_ba98_7654_3210,
+        -0x7654_3210_fedc_ba98_7654_3210,
+    ];
+
+    #[test]
+    fn operators_match_i128() {
+        for &a in SAMPLES {
+            for &b in SAMPLES {
+                let (x, y) = (BigInt::from(a), BigInt::from(b));
+
+                assert_eq!(x.cmp(&y), a.cmp(&b));
+                assert_eq!(&x + &y, BigInt::from(a + b));
+                assert_eq!(&x - &y, BigInt::from(a - b));
+                assert_eq!(&x & &y, BigInt::from(a & b));
+                assert_eq!(&x | &y, BigInt::from(a | b));
+                assert_eq!(&x ^ &y, BigInt::from(a ^ b));
+
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(&x * &y, BigInt::from(product));
+                }
+
+                if let (Some(quotient), Some(remainder)) = (a.checked_div(b), a.checked_rem(b)) {
+                    assert_eq!(&x / &y, BigInt::from(quotient));
+                    assert_eq!(&x % &y, BigInt::from(remainder));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unary_operators_match_i128() {
+        for &a in SAMPLES {
+            assert_eq!(-BigInt::from(a), BigInt::from(-a));
+            assert_eq!(!&BigInt::from(a), BigInt::from(!a));
+        }
+    }
+
+    #[test]
+    fn shifts_match_i128() {
+        for &a in SAMPLES {
+            for &amount in &[0, 1, 7, 32, 33, 20] {
+                assert_eq!(&BigInt::from(a) >> amount, BigInt::from(a >> amount));
+            }
+        }
+
+        assert_eq!(&BigInt::from(-1) >> 1000, BigInt::from(-1));
+        assert_eq!(&BigInt::from(-3) << 2, BigInt::from(-12));
+        assert_eq!(&BigInt::from(1) << 127, BigInt::from(1_u128 << 127));
+    }
+
+    #[test]
+    fn arithmetic_beyond_i128() {
+        let big = &BigInt::from(u128::MAX) + &BigInt::from(1);
+
+        assert_eq!(big.to_string(), "340282366920938463463374607431768211456");
+        assert_eq!(
+            (&big * &big).to_string(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+        );
+        assert_eq!(&(&big * &big) / &big, big);
+        assert_eq!(&(&big * &big) % &(&big - &BigInt::from(1)), BigInt::from(1));
+        assert_eq!(&-&big | &BigInt::from(5), &BigInt::from(5) - &big);
+    }
+}
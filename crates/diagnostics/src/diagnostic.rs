@@ -0,0 +1,153 @@
+use crate::{LineCol, LineIndex};
+use std::fmt;
+use text_size::TextRange;
+
+/// A problem found in some source code, produced by any stage of the compiler.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// A stable identifier for the kind of problem, such as `E0001`, which can be passed to
+    /// `eldiro explain` to learn more about it
+    pub code: &'static str,
+    pub range: TextRange,
+    pub message: String,
+    /// Shown next to the carets underneath `range`
+    pub label: Option<String>,
+    pub secondary_labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    pub fn error(code: &'static str, message: impl Into<String>, range: TextRange) -> Self {
+        Self {
+            severity: Severity::Error,
+            code,
+            range,
+            message: message.into(),
+            label: None,
+            secondary_labels: Vec::new(),
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    pub fn with_secondary_label(mut self, range: TextRange, message: impl Into<String>) -> Self {
+        self.secondary_labels.push(Label {
+            range,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    pub fn with_suggestion(
+        mut self,
+        message: impl Into<String>,
+        range: TextRange,
+        replacement: impl Into<String>,
+    ) -> Self {
+        self.suggestions.push(Suggestion {
+            message: message.into(),
+            range,
+            replacement: replacement.into(),
+        });
+        self
+    }
+
+    /// Displays the diagnostic on a single line, prefixed by the `file:line:column` it refers to.
+    pub fn display<'a>(
+        &'a self,
+        file: &'a str,
+        line_index: &'a LineIndex,
+    ) -> impl fmt::Display + 'a {
+        Located {
+            diagnostic: self,
+            file,
+            line_index,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Label {
+    pub range: TextRange,
+    pub message: String,
+}
+
+/// A fix for a diagnostic that can be applied automatically by replacing the text in `range` with
+/// `replacement`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub message: String,
+    pub range: TextRange,
+    pub replacement: String,
+}
+
+struct Located<'a> {
+    diagnostic: &'a Diagnostic,
+    file: &'a str,
+    line_index: &'a LineIndex,
+}
+
+impl fmt::Display for Located<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LineCol { line, col } = self.line_index.line_col(self.diagnostic.range.start());
+
+        // Lines and columns are zero-based internally, but everybody expects them to be one-based.
+        write!(
+            f,
+            "{}:{}:{}: {}[{}]: {}",
+            self.file,
+            line + 1,
+            col + 1,
+            self.diagnostic.severity,
+            self.diagnostic.code,
+            self.diagnostic.message,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_line_and_column() {
+        let line_index = LineIndex::new("let a = 1\nlet b = c\n");
+        let diagnostic = Diagnostic::error(
+            "E0000",
+            "undefined variable",
+            TextRange::new(18.into(), 19.into()),
+        );
+
+        assert_eq!(
+            diagnostic.display("main.eldiro", &line_index).to_string(),
+            "main.eldiro:2:9: error[E0000]: undefined variable",
+        );
+    }
+}
@@ -0,0 +1,53 @@
+/// Longer explanations of each error code, which are shown by `eldiro explain`.
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("E0001", include_str!("explanations/E0001.md")),
+    ("E0002", include_str!("explanations/E0002.md")),
+    ("E0003", include_str!("explanations/E0003.md")),
+    ("E0004", include_str!("explanations/E0004.md")),
+    ("E0005", include_str!("explanations/E0005.md")),
+    ("E0006", include_str!("explanations/E0006.md")),
+    ("E0007", include_str!("explanations/E0007.md")),
+    ("E0008", include_str!("explanations/E0008.md")),
+    ("E0009", include_str!("explanations/E0009.md")),
+    ("E0010", include_str!("explanations/E0010.md")),
+    ("E0011", include_str!("explanations/E0011.md")),
+    ("E0012", include_str!("explanations/E0012.md")),
+    ("E0013", include_str!("explanations/E0013.md")),
+    ("E0014", include_str!("explanations/E0014.md")),
+];
+
+/// Returns the explanation for `code`, if it is a known error code. Codes are matched
+/// case-insensitively so that both `E0001` and `e0001` are accepted.
+pub fn explain(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(c, _)| c.eq_ignore_ascii_case(code))
+        .map(|(_, explanation)| *explanation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_known_code() {
+        assert!(explain("E0001").unwrap().starts_with("The parser found"));
+    }
+
+    #[test]
+    fn explain_lowercase_code() {
+        assert_eq!(explain("e0002"), explain("E0002"));
+    }
+
+    #[test]
+    fn explain_unknown_code() {
+        assert_eq!(explain("E9999"), None);
+    }
+
+    #[test]
+    fn codes_are_sequential() {
+        for (idx, (code, _)) in EXPLANATIONS.iter().enumerate() {
+            assert_eq!(*code, format!("E{:04}", idx + 1));
+        }
+    }
+}
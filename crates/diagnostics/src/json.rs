@@ -0,0 +1,173 @@
+use crate::{Diagnostic, LineCol, LineIndex};
+use std::fmt::Write;
+use text_size::{TextRange, TextSize};
+
+/// Serializes `diagnostic` as a single-line JSON object for consumption by other tools.
+///
+/// Byte offsets are zero-based, while lines and columns are one-based. Columns are given both in
+/// UTF-8 bytes (`column`) and UTF-16 code units (`column_utf16`).
+pub fn to_json(diagnostic: &Diagnostic, file: &str, line_index: &LineIndex) -> String {
+    let mut s = String::new();
+
+    s.push_str("{\"file\":");
+    write_string(&mut s, file);
+    write!(s, ",\"severity\":\"{}\"", diagnostic.severity).unwrap();
+    s.push_str(",\"code\":");
+    write_string(&mut s, diagnostic.code);
+    s.push_str(",\"message\":");
+    write_string(&mut s, &diagnostic.message);
+    s.push(',');
+    write_span(&mut s, diagnostic.range, line_index);
+
+    s.push_str(",\"label\":");
+    match &diagnostic.label {
+        Some(label) => write_string(&mut s, label),
+        None => s.push_str("null"),
+    }
+
+    s.push_str(",\"secondary_labels\":[");
+    for (idx, label) in diagnostic.secondary_labels.iter().enumerate() {
+        if idx != 0 {
+            s.push(',');
+        }
+
+        s.push_str("{\"message\":");
+        write_string(&mut s, &label.message);
+        s.push(',');
+        write_span(&mut s, label.range, line_index);
+        s.push('}');
+    }
+
+    s.push_str("],\"notes\":[");
+    for (idx, note) in diagnostic.notes.iter().enumerate() {
+        if idx != 0 {
+            s.push(',');
+        }
+
+        write_string(&mut s, note);
+    }
+
+    s.push_str("],\"suggestions\":[");
+    for (idx, suggestion) in diagnostic.suggestions.iter().enumerate() {
+        if idx != 0 {
+            s.push(',');
+        }
+
+        s.push_str("{\"message\":");
+        write_string(&mut s, &suggestion.message);
+        s.push(',');
+        write_span(&mut s, suggestion.range, line_index);
+        s.push_str(",\"replacement\":");
+        write_string(&mut s, &suggestion.replacement);
+        s.push('}');
+    }
+
+    s.push_str("]}");
+
+    s
+}
+
+fn write_span(s: &mut String, range: TextRange, line_index: &LineIndex) {
+    write!(
+        s,
+        "\"range\":{{\"start\":{},\"end\":{}}},\"start\":",
+        u32::from(range.start()),
+        u32::from(range.end()),
+    )
+    .unwrap();
+    write_position(s, range.start(), line_index);
+    s.push_str(",\"end\":");
+    write_position(s, range.end(), line_index);
+}
+
+fn write_position(s: &mut String, offset: TextSize, line_index: &LineIndex) {
+    let LineCol { line, col } = line_index.line_col(offset);
+    let col_utf16 = line_index.line_col_utf16(offset).col;
+
+    write!(
+        s,
+        "{{\"line\":{},\"column\":{},\"column_utf16\":{}}}",
+        line + 1,
+        col + 1,
+        col_utf16 + 1,
+    )
+    .unwrap();
+}
+
+fn write_string(s: &mut String, string: &str) {
+    s.push('"');
+
+    for c in string.chars() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            c if c.is_control() => write!(s, "\\u{:04x}", c as u32).unwrap(),
+            c => s.push(c),
+        }
+    }
+
+    s.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::{expect, Expect};
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(start.into(), end.into())
+    }
+
+    fn check(text: &str, diagnostic: Diagnostic, expected: Expect) {
+        let line_index = LineIndex::new(text);
+        expected.assert_eq(&to_json(&diagnostic, "main.eldiro", &line_index));
+    }
+
+    #[test]
+    fn serialize_minimal_diagnostic() {
+        check(
+            "let = 10",
+            Diagnostic::error("E0001", "expected identifier, but found ‘=’", range(4, 5)),
+            expect![[
+                r#"{"file":"main.eldiro","severity":"error","code":"E0001","message":"expected identifier, but found ‘=’","range":{"start":4,"end":5},"start":{"line":1,"column":5,"column_utf16":5},"end":{"line":1,"column":6,"column_utf16":6},"label":null,"secondary_labels":[],"notes":[],"suggestions":[]}"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn serialize_full_diagnostic() {
+        check(
+            "a\n(1",
+            Diagnostic::error("E0001", "expected ‘)’", range(3, 4))
+                .with_label("here")
+                .with_secondary_label(range(2, 3), "opened here")
+                .with_note("the end of the input was reached")
+                .with_suggestion("insert ‘)’", range(4, 4), ")"),
+            expect![[
+                r#"{"file":"main.eldiro","severity":"error","code":"E0001","message":"expected ‘)’","range":{"start":3,"end":4},"start":{"line":2,"column":2,"column_utf16":2},"end":{"line":2,"column":3,"column_utf16":3},"label":"here","secondary_labels":[{"message":"opened here","range":{"start":2,"end":3},"start":{"line":2,"column":1,"column_utf16":1},"end":{"line":2,"column":2,"column_utf16":2}}],"notes":["the end of the input was reached"],"suggestions":[{"message":"insert ‘)’","range":{"start":4,"end":4},"start":{"line":2,"column":3,"column_utf16":3},"end":{"line":2,"column":3,"column_utf16":3},"replacement":")"}]}"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn escape_strings() {
+        let mut s = String::new();
+        write_string(&mut s, "a \"quoted\" \\ line\nbreak\u{1}");
+
+        assert_eq!(s, r#""a \"quoted\" \\ line\nbreak\u0001""#);
+    }
+
+    #[test]
+    fn utf16_columns_differ_after_wide_chars() {
+        check(
+            "# 🦀\nx",
+            Diagnostic::error("E0000", "bad", range(2, 6)),
+            expect![[
+                r#"{"file":"main.eldiro","severity":"error","code":"E0000","message":"bad","range":{"start":2,"end":6},"start":{"line":1,"column":3,"column_utf16":3},"end":{"line":1,"column":7,"column_utf16":5},"label":null,"secondary_labels":[],"notes":[],"suggestions":[]}"#
+            ]],
+        );
+    }
+}
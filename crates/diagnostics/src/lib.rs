@@ -0,0 +1,11 @@
+mod diagnostic;
+mod explanation;
+mod json;
+mod line_index;
+mod render;
+
+pub use diagnostic::{Diagnostic, Label, Severity, Suggestion};
+pub use explanation::explain;
+pub use json::to_json;
+pub use line_index::{LineCol, LineIndex};
+pub use render::Renderer;
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use text_size::{TextRange, TextSize};
+
+/// Converts between byte offsets into some text and line/column positions.
+#[derive(Debug, PartialEq)]
+pub struct LineIndex {
+    /// The offset at which each line starts
+    line_starts: Vec<TextSize>,
+    /// The non-ASCII characters on each line, used to calculate UTF-16 columns
+    wide_chars: HashMap<u32, Vec<WideChar>>,
+    len: TextSize,
+}
+
+/// A zero-based line and column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineCol {
+    pub line: u32,
+    pub col: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WideChar {
+    /// The range of the character relative to the start of its line
+    range: TextRange,
+}
+
+impl WideChar {
+    fn len_utf16(&self) -> u32 {
+        if u32::from(self.range.len()) == 4 {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let mut line_starts = vec![TextSize::from(0)];
+        let mut wide_chars = HashMap::new();
+
+        let mut line = 0;
+        let mut line_start = TextSize::from(0);
+
+        for (idx, c) in text.char_indices() {
+            let offset = TextSize::try_from(idx).unwrap();
+
+            if c == '\n' {
+                line += 1;
+                line_start = offset + TextSize::of(c);
+                line_starts.push(line_start);
+                continue;
+            }
+
+            if !c.is_ascii() {
+                let start = offset - line_start;
+
+                wide_chars
+                    .entry(line)
+                    .or_insert_with(Vec::new)
+                    .push(WideChar {
+                        range: TextRange::at(start, TextSize::of(c)),
+                    });
+            }
+        }
+
+        Self {
+            line_starts,
+            wide_chars,
+            len: TextSize::of(text),
+        }
+    }
+
+    /// Returns the line and the column in UTF-8 bytes that `offset` lies at.
+    pub fn line_col(&self, offset: TextSize) -> LineCol {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let col = offset - self.line_starts[line];
+
+        LineCol {
+            line: line as u32,
+            col: col.into(),
+        }
+    }
+
+    /// Returns the line and the column in UTF-16 code units that `offset` lies at, as is expected
+    /// by most editors.
+    pub fn line_col_utf16(&self, offset: TextSize) -> LineCol {
+        let LineCol { line, col } = self.line_col(offset);
+        let mut col_utf16 = col;
+
+        if let Some(wide_chars) = self.wide_chars.get(&line) {
+            for wide_char in wide_chars {
+                if u32::from(wide_char.range.start()) >= col {
+                    break;
+                }
+
+                col_utf16 -= u32::from(wide_char.range.len()) - wide_char.len_utf16();
+            }
+        }
+
+        LineCol {
+            line,
+            col: col_utf16,
+        }
+    }
+
+    /// Returns the range of `line`, excluding its trailing newline.
+    pub fn line_range(&self, line: u32) -> TextRange {
+        let start = self.line_starts[line as usize];
+
+        let end = match self.line_starts.get(line as usize + 1) {
+            Some(next_line_start) => *next_line_start - TextSize::of('\n'),
+            None => self.len,
+        };
+
+        TextRange::new(start, end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(text: &str, offset: u32, line: u32, col: u32, col_utf16: u32) {
+        let line_index = LineIndex::new(text);
+        let offset = TextSize::from(offset);
+
+        assert_eq!(line_index.line_col(offset), LineCol { line, col });
+        assert_eq!(
+            line_index.line_col_utf16(offset),
+            LineCol {
+                line,
+                col: col_utf16,
+            },
+        );
+    }
+
+    #[test]
+    fn start_of_text() {
+        check("let a = 1", 0, 0, 0, 0);
+    }
+
+    #[test]
+    fn middle_of_first_line() {
+        check("let a = 1", 4, 0, 4, 4);
+    }
+
+    #[test]
+    fn start_of_second_line() {
+        check("let a = 1\na", 10, 1, 0, 0);
+    }
+
+    #[test]
+    fn newline_is_on_line_it_ends() {
+        check("let a = 1\na", 9, 0, 9, 9);
+    }
+
+    #[test]
+    fn end_of_text() {
+        check("a\nbc", 4, 1, 2, 2);
+    }
+
+    #[test]
+    fn after_two_byte_char() {
+        check("# é\n# é x", 10, 1, 5, 4);
+    }
+
+    #[test]
+    fn after_four_byte_char() {
+        check("# 🦀 x", 7, 0, 7, 5);
+    }
+
+    #[test]
+    fn before_wide_char() {
+        check("a 🦀", 2, 0, 2, 2);
+    }
+
+    #[test]
+    fn range_of_line() {
+        let line_index = LineIndex::new("let a = 1\na\n");
+
+        assert_eq!(line_index.line_range(0), TextRange::new(0.into(), 9.into()));
+        assert_eq!(
+            line_index.line_range(1),
+            TextRange::new(10.into(), 11.into())
+        );
+        assert_eq!(
+            line_index.line_range(2),
+            TextRange::new(12.into(), 12.into())
+        );
+    }
+}
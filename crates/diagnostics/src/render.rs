@@ -0,0 +1,306 @@
+use crate::{Diagnostic, LineCol, LineIndex, Severity};
+use std::fmt::Write;
+use text_size::{TextRange, TextSize};
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Renders diagnostics as the lines of source they refer to, with the relevant ranges underlined.
+pub struct Renderer<'a> {
+    file: &'a str,
+    text: &'a str,
+    line_index: LineIndex,
+    color: bool,
+}
+
+struct Marker<'a> {
+    line: u32,
+    range: TextRange,
+    label: Option<&'a str>,
+    is_primary: bool,
+}
+
+impl<'a> Renderer<'a> {
+    pub fn new(file: &'a str, text: &'a str, color: bool) -> Self {
+        Self {
+            file,
+            text,
+            line_index: LineIndex::new(text),
+            color,
+        }
+    }
+
+    pub fn render(&self, diagnostic: &Diagnostic) -> String {
+        let mut markers = vec![self.marker(diagnostic.range, diagnostic.label.as_deref(), true)];
+        markers.extend(
+            diagnostic
+                .secondary_labels
+                .iter()
+                .map(|label| self.marker(label.range, Some(&label.message), false)),
+        );
+        markers.sort_by_key(|marker| (marker.line, marker.range.start()));
+
+        let last_line = markers.iter().map(|marker| marker.line).max().unwrap();
+        let gutter_width = (last_line + 1).to_string().len();
+        let padding = " ".repeat(gutter_width);
+
+        let mut s = String::new();
+        let LineCol { line, col } = self.line_index.line_col(diagnostic.range.start());
+
+        let (severity_style, underline_style) = match diagnostic.severity {
+            Severity::Error => (RED, RED),
+            Severity::Warning => (YELLOW, YELLOW),
+        };
+
+        writeln!(
+            s,
+            "{}: {}",
+            self.paint(
+                severity_style,
+                &format!("{}[{}]", diagnostic.severity, diagnostic.code),
+            ),
+            self.paint(BOLD, &diagnostic.message),
+        )
+        .unwrap();
+        writeln!(
+            s,
+            "{}{} {}:{}:{}",
+            padding,
+            self.paint(BLUE, "-->"),
+            self.file,
+            line + 1,
+            col + 1,
+        )
+        .unwrap();
+        write!(s, "{} {}", padding, self.paint(BLUE, "|")).unwrap();
+
+        let mut previous_line = None;
+
+        for marker in &markers {
+            if previous_line != Some(marker.line) {
+                let line_range = self.line_index.line_range(marker.line);
+                let line_number = format!("{:>1$} |", marker.line + 1, gutter_width);
+                let line_text = self.text[line_range].trim_end_matches('\r');
+
+                write!(s, "\n{}", self.paint(BLUE, &line_number)).unwrap();
+
+                if !line_text.is_empty() {
+                    write!(s, " {}", line_text).unwrap();
+                }
+
+                previous_line = Some(marker.line);
+            }
+
+            let line_start = self.line_index.line_range(marker.line).start();
+            let indent = self.width(TextRange::new(line_start, marker.range.start()));
+            let underline =
+                if marker.is_primary { "^" } else { "-" }.repeat(self.width(marker.range).max(1));
+            let style = if marker.is_primary {
+                underline_style
+            } else {
+                BLUE
+            };
+
+            let underline = match marker.label {
+                Some(label) => format!("{} {}", underline, label),
+                None => underline,
+            };
+
+            write!(
+                s,
+                "\n{} {} {}{}",
+                padding,
+                self.paint(BLUE, "|"),
+                " ".repeat(indent),
+                self.paint(style, &underline),
+            )
+            .unwrap();
+        }
+
+        for note in &diagnostic.notes {
+            write!(
+                s,
+                "\n{} {} {}: {}",
+                padding,
+                self.paint(BLUE, "="),
+                self.paint(BOLD, "note"),
+                note,
+            )
+            .unwrap();
+        }
+
+        for suggestion in &diagnostic.suggestions {
+            write!(
+                s,
+                "\n{} {} {}: {}",
+                padding,
+                self.paint(BLUE, "="),
+                self.paint(BOLD, "help"),
+                suggestion.message,
+            )
+            .unwrap();
+        }
+
+        s
+    }
+
+    /// Creates a marker for `range`, which is truncated to the end of the line it starts on.
+    fn marker(&self, range: TextRange, label: Option<&'a str>, is_primary: bool) -> Marker<'a> {
+        let line = self.line_index.line_col(range.start()).line;
+        let line_end = self.line_index.line_range(line).end();
+        let end = range.end().min(line_end).max(range.start());
+
+        Marker {
+            line,
+            range: TextRange::new(range.start(), end),
+            label,
+            is_primary,
+        }
+    }
+
+    /// Returns the number of columns `range` takes up when printed.
+    fn width(&self, range: TextRange) -> usize {
+        let end = range.end().min(TextSize::of(self.text));
+        self.text[TextRange::new(range.start(), end)]
+            .chars()
+            .count()
+    }
+
+    fn paint(&self, style: &str, s: &str) -> String {
+        if self.color {
+            format!("{}{}{}", style, s, RESET)
+        } else {
+            s.to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use expect_test::{expect, Expect};
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(start.into(), end.into())
+    }
+
+    fn check(text: &str, diagnostic: Diagnostic, expected: Expect) {
+        let renderer = Renderer::new("main.eldiro", text, false);
+        expected.assert_eq(&renderer.render(&diagnostic));
+    }
+
+    #[test]
+    fn render_single_line() {
+        check(
+            "let = 10",
+            Diagnostic::error("E0000", "expected identifier, but found ‘=’", range(4, 5)),
+            expect![[r#"
+error[E0000]: expected identifier, but found ‘=’
+ --> main.eldiro:1:5
+  |
+1 | let = 10
+  |     ^"#]],
+        );
+    }
+
+    #[test]
+    fn render_label_and_note() {
+        check(
+            "1 +\n  99999999999999999999",
+            Diagnostic::error("E0000", "number literal is too large", range(6, 26))
+                .with_label("this literal")
+                .with_note("the largest integer is 18446744073709551615"),
+            expect![[r#"
+error[E0000]: number literal is too large
+ --> main.eldiro:2:3
+  |
+2 |   99999999999999999999
+  |   ^^^^^^^^^^^^^^^^^^^^ this literal
+  = note: the largest integer is 18446744073709551615"#]],
+        );
+    }
+
+    #[test]
+    fn render_suggestion() {
+        check(
+            "(1 + 2",
+            Diagnostic::error("E0000", "expected ‘)’", range(5, 6)).with_suggestion(
+                "insert ‘)’",
+                range(6, 6),
+                ")",
+            ),
+            expect![[r#"
+error[E0000]: expected ‘)’
+ --> main.eldiro:1:6
+  |
+1 | (1 + 2
+  |      ^
+  = help: insert ‘)’"#]],
+        );
+    }
+
+    #[test]
+    fn render_secondary_labels() {
+        check(
+            "let a = 1\nlet b = 2\na = b",
+            Diagnostic::error("E0000", "cannot assign twice", range(20, 21))
+                .with_label("assignment here")
+                .with_secondary_label(range(4, 5), "first defined here")
+                .with_secondary_label(range(24, 25), "value used here"),
+            expect![[r#"
+error[E0000]: cannot assign twice
+ --> main.eldiro:3:1
+  |
+1 | let a = 1
+  |     - first defined here
+3 | a = b
+  | ^ assignment here
+  |     - value used here"#]],
+        );
+    }
+
+    #[test]
+    fn render_range_spanning_multiple_lines() {
+        check(
+            "(1 +\n2",
+            Diagnostic::error("E0000", "unbalanced", range(0, 6)),
+            expect![[r#"
+error[E0000]: unbalanced
+ --> main.eldiro:1:1
+  |
+1 | (1 +
+  | ^^^^"#]],
+        );
+    }
+
+    #[test]
+    fn render_wide_gutter() {
+        check(
+            &"\n".repeat(10),
+            Diagnostic::error("E0000", "bad", range(9, 10)),
+            expect![[r#"
+error[E0000]: bad
+  --> main.eldiro:10:1
+   |
+10 |
+   | ^"#]],
+        );
+    }
+
+    #[test]
+    fn render_with_color() {
+        let renderer = Renderer::new("main.eldiro", "x", true);
+
+        assert_eq!(
+            renderer.render(&Diagnostic::error("E0000", "bad", range(0, 1))),
+            "\x1b[1;31merror[E0000]\x1b[0m: \x1b[1mbad\x1b[0m
+ \x1b[1;34m-->\x1b[0m main.eldiro:1:1
+  \x1b[1;34m|\x1b[0m
+\x1b[1;34m1 |\x1b[0m x
+  \x1b[1;34m|\x1b[0m \x1b[1;31m^\x1b[0m",
+        );
+    }
+}
@@ -0,0 +1,255 @@
+use eval::OverflowMode;
+use std::fmt;
+
+pub(crate) const USAGE: &str = "\
+usage: eldiro [<command> <argument>]
+
+commands:
+    run <file>      evaluate <file> and print the result
+                    (pass --overflow=wrapping or --overflow=saturating to
+                    change what happens when integer arithmetic overflows)
+    check <file>    report any errors in <file> without evaluating it
+                    (pass --message-format=json for machine-readable output)
+    parse <file>    show the syntax tree <file> is parsed into
+    lex <file>      show the tokens <file> is lexed into
+    explain <code>  show a detailed explanation of the error code <code>
+
+Running eldiro without a command starts the REPL, where the overflow mode can
+be changed with ‘:overflow <mode>’.";
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Cli<'a> {
+    Repl,
+    Run(&'a str, OverflowMode),
+    Check(&'a str, MessageFormat),
+    Parse(&'a str),
+    Lex(&'a str),
+    Explain(&'a str),
+}
+
+/// How `check` prints diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum MessageFormat {
+    Human,
+    /// One JSON object per line for each diagnostic
+    Json,
+}
+
+impl<'a> Cli<'a> {
+    pub(crate) fn parse(args: &'a [String]) -> Result<Self, CliError<'a>> {
+        let (command, rest) = match args.split_first() {
+            Some((command, rest)) => (command.as_str(), rest),
+            None => return Ok(Self::Repl),
+        };
+
+        let argument_name = match command {
+            "run" | "check" | "parse" | "lex" => "a file name",
+            "explain" => "an error code",
+            _ => return Err(CliError::UnknownCommand(command)),
+        };
+
+        let mut message_format = None;
+        let mut overflow_mode = None;
+        let mut arguments = Vec::new();
+
+        for arg in rest {
+            if let Some(format) = arg.strip_prefix("--message-format=") {
+                message_format = match format {
+                    _ if command != "check" => return Err(CliError::UnexpectedArgument(arg)),
+                    "human" => Some(MessageFormat::Human),
+                    "json" => Some(MessageFormat::Json),
+                    _ => return Err(CliError::UnknownMessageFormat(format)),
+                };
+            } else if let Some(mode) = arg.strip_prefix("--overflow=") {
+                overflow_mode = match OverflowMode::from_name(mode) {
+                    _ if command != "run" => return Err(CliError::UnexpectedArgument(arg)),
+                    Some(mode) => Some(mode),
+                    None => return Err(CliError::UnknownOverflowMode(mode)),
+                };
+            } else {
+                arguments.push(arg.as_str());
+            }
+        }
+
+        let argument = match arguments.as_slice() {
+            [argument] => argument,
+            [] => return Err(CliError::MissingArgument(command, argument_name)),
+            [_, extra, ..] => return Err(CliError::UnexpectedArgument(extra)),
+        };
+
+        let cli = match command {
+            "run" => Self::Run(argument, overflow_mode.unwrap_or_default()),
+            "check" => Self::Check(argument, message_format.unwrap_or(MessageFormat::Human)),
+            "parse" => Self::Parse(argument),
+            "lex" => Self::Lex(argument),
+            "explain" => Self::Explain(argument),
+            _ => unreachable!(),
+        };
+
+        Ok(cli)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum CliError<'a> {
+    UnknownCommand(&'a str),
+    MissingArgument(&'a str, &'static str),
+    UnexpectedArgument(&'a str),
+    UnknownMessageFormat(&'a str),
+    UnknownOverflowMode(&'a str),
+}
+
+impl fmt::Display for CliError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCommand(command) => write!(f, "unknown command ‘{}’", command),
+            Self::MissingArgument(command, argument_name) => {
+                write!(f, "‘{}’ requires {}", command, argument_name)
+            }
+            Self::UnexpectedArgument(argument) => write!(f, "unexpected argument ‘{}’", argument),
+            Self::UnknownMessageFormat(format) => write!(
+                f,
+                "unknown message format ‘{}’; expected ‘human’ or ‘json’",
+                format,
+            ),
+            Self::UnknownOverflowMode(mode) => write!(
+                f,
+                "unknown overflow mode ‘{}’; expected ‘checked’, ‘wrapping’ or ‘saturating’",
+                mode,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(args: &[&str], expected: Result<Cli, CliError>) {
+        let args: Vec<_> = args.iter().map(|arg| arg.to_string()).collect();
+        assert_eq!(Cli::parse(&args), expected);
+    }
+
+    #[test]
+    fn parse_no_arguments() {
+        check(&[], Ok(Cli::Repl));
+    }
+
+    #[test]
+    fn parse_run() {
+        check(
+            &["run", "foo.eldiro"],
+            Ok(Cli::Run("foo.eldiro", OverflowMode::Checked)),
+        );
+    }
+
+    #[test]
+    fn parse_run_with_overflow_mode() {
+        check(
+            &["run", "--overflow=wrapping", "foo.eldiro"],
+            Ok(Cli::Run("foo.eldiro", OverflowMode::Wrapping)),
+        );
+        check(
+            &["run", "foo.eldiro", "--overflow=saturating"],
+            Ok(Cli::Run("foo.eldiro", OverflowMode::Saturating)),
+        );
+    }
+
+    #[test]
+    fn parse_run_with_unknown_overflow_mode() {
+        check(
+            &["run", "--overflow=panicking", "foo.eldiro"],
+            Err(CliError::UnknownOverflowMode("panicking")),
+        );
+    }
+
+    #[test]
+    fn parse_overflow_mode_for_other_command() {
+        check(
+            &["check", "--overflow=wrapping", "foo.eldiro"],
+            Err(CliError::UnexpectedArgument("--overflow=wrapping")),
+        );
+    }
+
+    #[test]
+    fn parse_check() {
+        check(
+            &["check", "foo.eldiro"],
+            Ok(Cli::Check("foo.eldiro", MessageFormat::Human)),
+        );
+    }
+
+    #[test]
+    fn parse_check_with_json_message_format() {
+        check(
+            &["check", "--message-format=json", "foo.eldiro"],
+            Ok(Cli::Check("foo.eldiro", MessageFormat::Json)),
+        );
+    }
+
+    #[test]
+    fn parse_check_with_message_format_after_file() {
+        check(
+            &["check", "foo.eldiro", "--message-format=human"],
+            Ok(Cli::Check("foo.eldiro", MessageFormat::Human)),
+        );
+    }
+
+    #[test]
+    fn parse_check_with_unknown_message_format() {
+        check(
+            &["check", "--message-format=xml", "foo.eldiro"],
+            Err(CliError::UnknownMessageFormat("xml")),
+        );
+    }
+
+    #[test]
+    fn parse_message_format_for_other_command() {
+        check(
+            &["run", "--message-format=json", "foo.eldiro"],
+            Err(CliError::UnexpectedArgument("--message-format=json")),
+        );
+    }
+
+    #[test]
+    fn parse_explain() {
+        check(&["explain", "E0001"], Ok(Cli::Explain("E0001")));
+    }
+
+    #[test]
+    fn parse_explain_without_code() {
+        check(
+            &["explain"],
+            Err(CliError::MissingArgument("explain", "an error code")),
+        );
+    }
+
+    #[test]
+    fn parse_command_without_file() {
+        check(
+            &["lex"],
+            Err(CliError::MissingArgument("lex", "a file name")),
+        );
+    }
+
+    #[test]
+    fn parse_command_with_extra_argument() {
+        check(
+            &["parse", "a.eldiro", "b.eldiro"],
+            Err(CliError::UnexpectedArgument("b.eldiro")),
+        );
+    }
+
+    #[test]
+    fn parse_unknown_command_without_file() {
+        check(&["build"], Err(CliError::UnknownCommand("build")));
+    }
+
+    #[test]
+    fn parse_unknown_command() {
+        check(
+            &["build", "a.eldiro"],
+            Err(CliError::UnknownCommand("build")),
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use eval::OverflowMode;
+use std::fmt;
+
+pub(crate) const HELP: &str = "\
+:tokens <input>   show the tokens <input> is lexed into
+:tree <input>     show the syntax tree <input> is parsed into
+:ast <input>      show the AST nodes of <input>
+:hir <input>      show the HIR <input> is lowered into
+:load <file>      evaluate the contents of <file>
+:save <file>      save everything entered this session to <file>
+:overflow <mode>  set what happens when integer arithmetic overflows
+                  (‘checked’, the default, ‘wrapping’ or ‘saturating’)
+:reset            forget everything entered this session
+:help             show this message
+:quit             exit the REPL";
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Command<'a> {
+    Tokens(&'a str),
+    Tree(&'a str),
+    Ast(&'a str),
+    Hir(&'a str),
+    Load(&'a str),
+    Save(&'a str),
+    Overflow(OverflowMode),
+    Reset,
+    Help,
+    Quit,
+}
+
+impl<'a> Command<'a> {
+    /// Parses a line of REPL input that starts with a colon.
+    pub(crate) fn parse(input: &'a str) -> Result<Self, CommandError<'a>> {
+        let input = input.trim();
+        assert!(input.starts_with(':'));
+
+        let (name, argument) = match input.find(char::is_whitespace) {
+            Some(idx) => (&input[1..idx], input[idx..].trim()),
+            None => (&input[1..], ""),
+        };
+
+        let command = match name {
+            "tokens" => Self::Tokens(argument),
+            "tree" => Self::Tree(argument),
+            "ast" => Self::Ast(argument),
+            "hir" => Self::Hir(argument),
+            "load" => Self::Load(required(name, argument, "a file name")?),
+            "save" => Self::Save(required(name, argument, "a file name")?),
+            "overflow" => {
+                let mode = required(name, argument, "an overflow mode")?;
+                let mode =
+                    OverflowMode::from_name(mode).ok_or(CommandError::UnknownOverflowMode(mode))?;
+
+                Self::Overflow(mode)
+            }
+            "reset" => Self::Reset,
+            "help" => Self::Help,
+            "quit" => Self::Quit,
+            _ => return Err(CommandError::UnknownCommand(name)),
+        };
+
+        Ok(command)
+    }
+}
+
+fn required<'a>(
+    name: &'a str,
+    argument: &'a str,
+    argument_name: &'static str,
+) -> Result<&'a str, CommandError<'a>> {
+    if argument.is_empty() {
+        Err(CommandError::MissingArgument(name, argument_name))
+    } else {
+        Ok(argument)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum CommandError<'a> {
+    UnknownCommand(&'a str),
+    MissingArgument(&'a str, &'static str),
+    UnknownOverflowMode(&'a str),
+}
+
+impl fmt::Display for CommandError<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCommand(name) => write!(
+                f,
+                "unknown command ‘:{}’; enter ‘:help’ for a list of commands",
+                name,
+            ),
+            Self::MissingArgument(name, argument_name) => {
+                write!(f, "‘:{}’ requires {}", name, argument_name)
+            }
+            Self::UnknownOverflowMode(mode) => write!(
+                f,
+                "unknown overflow mode ‘{}’; expected ‘checked’, ‘wrapping’ or ‘saturating’",
+                mode,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(input: &str, expected: Result<Command, CommandError>) {
+        assert_eq!(Command::parse(input), expected);
+    }
+
+    #[test]
+    fn parse_command_without_argument() {
+        check(":quit", Ok(Command::Quit));
+    }
+
+    #[test]
+    fn parse_command_with_argument() {
+        check(":tree let a = 1 + 2", Ok(Command::Tree("let a = 1 + 2")));
+    }
+
+    #[test]
+    fn parse_command_surrounded_by_whitespace() {
+        check("  :load   foo.eldiro \n", Ok(Command::Load("foo.eldiro")));
+    }
+
+    #[test]
+    fn parse_command_missing_required_argument() {
+        check(
+            ":save",
+            Err(CommandError::MissingArgument("save", "a file name")),
+        );
+    }
+
+    #[test]
+    fn parse_overflow_command() {
+        check(
+            ":overflow wrapping",
+            Ok(Command::Overflow(OverflowMode::Wrapping)),
+        );
+    }
+
+    #[test]
+    fn parse_overflow_command_with_unknown_mode() {
+        check(
+            ":overflow panicking",
+            Err(CommandError::UnknownOverflowMode("panicking")),
+        );
+    }
+
+    #[test]
+    fn parse_unknown_command() {
+        check(":foo bar", Err(CommandError::UnknownCommand("foo")));
+    }
+}
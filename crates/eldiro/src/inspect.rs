@@ -0,0 +1,32 @@
+use crate::session::Session;
+use lexer::Lexer;
+
+pub(crate) fn print_tokens(input: &str) {
+    for token in Lexer::new(input) {
+        println!("{:?}@{:?} {:?}", token.kind, token.range, token.text);
+    }
+}
+
+pub(crate) fn print_tree(input: &str) {
+    println!("{}", parser::parse(input).debug_tree());
+}
+
+pub(crate) fn print_ast(input: &str) {
+    let root = ast::Root::cast(parser::parse(input).syntax()).unwrap();
+    println!("{:#?}", root.stmts().collect::<Vec<_>>());
+}
+
+/// Prints the statements `input` is lowered into in the context of `session`, followed by the
+/// expressions they refer to.
+pub(crate) fn print_hir(input: &str, session: &mut Session) {
+    let root = ast::Root::cast(parser::parse(input).syntax()).unwrap();
+    let (stmts, exprs) = session.lower_to_inspect(root);
+
+    for stmt in &stmts {
+        println!("{:?}", stmt);
+    }
+
+    for (idx, expr) in exprs {
+        println!("{:?} = {:?}", idx, expr);
+    }
+}
@@ -1,40 +1,154 @@
-use parser::parse;
-use std::io::{self, Write};
+mod cli;
+mod command;
+mod inspect;
+mod repl;
+mod session;
+
+use cli::{Cli, MessageFormat};
+use diagnostics::{Diagnostic, LineIndex, Renderer};
+use eval::{Evaluator, OverflowMode};
+use hir::{Database, Stmt};
+use std::io::{self, IsTerminal};
+use std::{env, fs, process};
+use typeck::TypeChecker;
 
 fn main() -> io::Result<()> {
-    let stdin = io::stdin();
-    let mut stdout = io::stdout();
+    let args: Vec<_> = env::args().skip(1).collect();
+
+    let cli = match Cli::parse(&args) {
+        Ok(cli) => cli,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, cli::USAGE);
+            process::exit(2);
+        }
+    };
 
-    let mut input = String::new();
+    let succeeded = match cli {
+        Cli::Repl => {
+            repl::run()?;
+            true
+        }
+        Cli::Run(path, overflow_mode) => {
+            read(path).is_some_and(|input| run(path, &input, overflow_mode))
+        }
+        Cli::Check(path, message_format) => {
+            read(path).is_some_and(|input| check(path, &input, message_format).is_some())
+        }
+        Cli::Parse(path) => read(path)
+            .map(|input| inspect::print_tree(&input))
+            .is_some(),
+        Cli::Lex(path) => read(path)
+            .map(|input| inspect::print_tokens(&input))
+            .is_some(),
+        Cli::Explain(code) => explain(code),
+    };
 
-    loop {
-        write!(stdout, "→ ")?;
-        stdout.flush()?;
+    if !succeeded {
+        process::exit(1);
+    }
 
-        stdin.read_line(&mut input)?;
+    Ok(())
+}
 
-        let parse = parse(&input);
-        println!("{}", parse.debug_tree());
+/// Color is only used when writing to a terminal, and can be disabled by setting `NO_COLOR`.
+fn use_color(stream: &impl IsTerminal) -> bool {
+    stream.is_terminal() && env::var_os("NO_COLOR").is_none()
+}
 
-        let syntax = parse.syntax();
+fn explain(code: &str) -> bool {
+    match diagnostics::explain(code) {
+        Some(explanation) => {
+            print!("{}", explanation);
+            true
+        }
+        None => {
+            eprintln!("error: ‘{}’ is not a known error code", code);
+            false
+        }
+    }
+}
 
-        for error in ast::validation::validate(&syntax) {
-            println!("{}", error);
+fn read(path: &str) -> Option<String> {
+    match fs::read_to_string(path) {
+        Ok(input) => Some(input),
+        Err(error) => {
+            eprintln!("error: could not read {}: {}", path, error);
+            None
         }
+    }
+}
 
-        let root = ast::Root::cast(syntax).unwrap();
+fn run(path: &str, input: &str, overflow_mode: OverflowMode) -> bool {
+    let (database, type_checker, stmts) = match check(path, input, MessageFormat::Human) {
+        Some(checked) => checked,
+        None => return false,
+    };
 
-        dbg!(root
-            .stmts()
-            .filter_map(|stmt| if let ast::Stmt::VariableDef(var_def) = stmt {
-                Some(var_def.value())
-            } else {
-                None
-            })
-            .collect::<Vec<_>>());
+    match Evaluator::new(overflow_mode).eval_stmts(&database, &type_checker, &stmts) {
+        Ok(result) => {
+            if let Some(value) = result {
+                println!("{}", value);
+            }
 
-        dbg!(hir::lower(root));
+            true
+        }
+        Err(error) => {
+            match error.to_diagnostic() {
+                Some(diagnostic) => {
+                    let renderer = Renderer::new(path, input, use_color(&io::stderr()));
+                    eprintln!("{}\n", renderer.render(&diagnostic));
+                }
+                None => eprintln!("{}: error: {}", path, error),
+            }
 
-        input.clear();
+            false
+        }
     }
 }
+
+/// Reports any errors in `input`, returning its HIR and types if there were none.
+fn check(
+    path: &str,
+    input: &str,
+    message_format: MessageFormat,
+) -> Option<(Database, TypeChecker, Vec<Stmt>)> {
+    let (root, mut diagnostics) = analyze(input);
+    let (database, stmts, lowering_diagnostics) = hir::lower(root);
+    diagnostics.extend(lowering_diagnostics);
+    let (type_checker, type_diagnostics) = typeck::check(&database, &stmts);
+    diagnostics.extend(type_diagnostics);
+
+    match message_format {
+        MessageFormat::Human => {
+            let renderer = Renderer::new(path, input, use_color(&io::stderr()));
+
+            for diagnostic in &diagnostics {
+                eprintln!("{}\n", renderer.render(diagnostic));
+            }
+        }
+        MessageFormat::Json => {
+            let line_index = LineIndex::new(input);
+
+            for diagnostic in &diagnostics {
+                println!("{}", diagnostics::to_json(diagnostic, path, &line_index));
+            }
+        }
+    }
+
+    if diagnostics.is_empty() {
+        Some((database, type_checker, stmts))
+    } else {
+        None
+    }
+}
+
+/// Parses and validates `input`, returning its AST along with any diagnostics.
+fn analyze(input: &str) -> (ast::Root, Vec<Diagnostic>) {
+    let parse = parser::parse(input);
+    let syntax = parse.syntax();
+
+    let mut diagnostics = parse.errors();
+    diagnostics.extend(ast::validation::validate(&syntax));
+
+    (ast::Root::cast(syntax).unwrap(), diagnostics)
+}
@@ -0,0 +1,109 @@
+use crate::command::{self, Command};
+use crate::inspect;
+use crate::session::Session;
+use diagnostics::{Diagnostic, Renderer};
+use std::fs;
+use std::io::{self, Write};
+
+pub(crate) fn run() -> io::Result<()> {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    let mut input = String::new();
+    let mut session = Session::default();
+
+    loop {
+        write!(stdout, "{}", if input.is_empty() { "→ " } else { "… " })?;
+        stdout.flush()?;
+
+        let line_start = input.len();
+
+        if stdin.read_line(&mut input)? == 0 {
+            return Ok(());
+        }
+
+        if input.trim_start().starts_with(':') {
+            match Command::parse(&input) {
+                Ok(Command::Quit) => return Ok(()),
+                Ok(command) => run_command(command, &mut session),
+                Err(error) => println!("error: {}", error),
+            }
+
+            input.clear();
+            continue;
+        }
+
+        // Keep reading lines until the input can be parsed or the user enters an empty line to
+        // force what they’ve written so far to be evaluated.
+        let entered_empty_line = input[line_start..].trim().is_empty();
+        if parser::parse(&input).is_incomplete() && !entered_empty_line {
+            continue;
+        }
+
+        eval("<input>", &input, &mut session);
+        input.clear();
+    }
+}
+
+fn run_command(command: Command, session: &mut Session) {
+    match command {
+        Command::Tokens(input) => inspect::print_tokens(input),
+        Command::Tree(input) => inspect::print_tree(input),
+        Command::Ast(input) => inspect::print_ast(input),
+        Command::Hir(input) => inspect::print_hir(input, session),
+        Command::Load(path) => match fs::read_to_string(path) {
+            Ok(contents) => eval(path, &contents, session),
+            Err(error) => println!("error: could not read {}: {}", path, error),
+        },
+        Command::Save(path) => {
+            if let Err(error) = fs::write(path, session.history()) {
+                println!("error: could not write {}: {}", path, error);
+            }
+        }
+        Command::Overflow(mode) => session.set_overflow_mode(mode),
+        Command::Reset => session.reset(),
+        Command::Help => println!("{}", command::HELP),
+        Command::Quit => unreachable!(),
+    }
+}
+
+/// Evaluates `input` in `session`, printing either the result or any errors, which are reported
+/// as having come from `file`.
+fn eval(file: &str, input: &str, session: &mut Session) {
+    let (root, diagnostics) = crate::analyze(input);
+
+    if !diagnostics.is_empty() {
+        print_diagnostics(file, input, &diagnostics);
+        return;
+    }
+
+    let stmts = match session.check(root) {
+        Ok(stmts) => stmts,
+        Err(diagnostics) => {
+            print_diagnostics(file, input, &diagnostics);
+            return;
+        }
+    };
+
+    match session.eval(&stmts) {
+        Ok(result) => {
+            if let Some((idx, value)) = result {
+                println!("[{}] {}", idx, value);
+            }
+
+            session.push_history(input);
+        }
+        Err(error) => match error.to_diagnostic() {
+            Some(diagnostic) => print_diagnostics(file, input, &[diagnostic]),
+            None => println!("error: {}", error),
+        },
+    }
+}
+
+fn print_diagnostics(file: &str, input: &str, diagnostics: &[Diagnostic]) {
+    let renderer = Renderer::new(file, input, crate::use_color(&io::stdout()));
+
+    for diagnostic in diagnostics {
+        println!("{}\n", renderer.render(diagnostic));
+    }
+}
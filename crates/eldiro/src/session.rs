@@ -0,0 +1,238 @@
+use diagnostics::Diagnostic;
+use eval::{EvalError, Evaluator, OverflowMode, Value};
+use hir::{Database, Expr, ExprIdx, Stmt};
+use typeck::TypeChecker;
+
+/// State that is kept across lines entered into the REPL.
+#[derive(Debug, Default)]
+pub(crate) struct Session {
+    database: Database,
+    type_checker: TypeChecker,
+    evaluator: Evaluator,
+    results: Vec<Value>,
+    history: String,
+}
+
+impl Session {
+    /// Lowers and type checks `root` in the context of everything that has been entered so far,
+    /// so that it can refer to the bindings defined by previous input. If there are any errors,
+    /// the bindings `root` defines are forgotten again, along with anything that was learnt about
+    /// the types of earlier bindings while checking it.
+    pub(crate) fn check(&mut self, root: ast::Root) -> Result<Vec<Stmt>, Vec<Diagnostic>> {
+        let saved_scopes = self.database.save_scopes();
+        let saved_type_checker = self.type_checker.clone();
+
+        let (stmts, mut diagnostics) = self.database.lower_root(root);
+
+        // Type errors in input that couldn’t be lowered would mostly repeat the lowering errors.
+        if diagnostics.is_empty() {
+            diagnostics = self.type_checker.check_stmts(&self.database, &stmts);
+        }
+
+        if diagnostics.is_empty() {
+            Ok(stmts)
+        } else {
+            self.database.restore_scopes(saved_scopes);
+            self.type_checker = saved_type_checker;
+
+            Err(diagnostics)
+        }
+    }
+
+    /// Lowers `root` in the context of everything that has been entered so far, for `:hir`, without
+    /// keeping the bindings it defines. Returns its statements along with the expressions lowered
+    /// from it.
+    pub(crate) fn lower_to_inspect(
+        &mut self,
+        root: ast::Root,
+    ) -> (Vec<Stmt>, Vec<(ExprIdx, &Expr)>) {
+        let saved_scopes = self.database.save_scopes();
+        let first_expr = self.database.exprs().len();
+
+        let (stmts, _) = self.database.lower_root(root);
+        self.database.restore_scopes(saved_scopes);
+
+        (stmts, self.database.exprs().skip(first_expr).collect())
+    }
+
+    /// Evaluates `stmts`, which must have been returned by `check`, returning the index and value
+    /// of the result if there was one.
+    pub(crate) fn eval(&mut self, stmts: &[Stmt]) -> Result<Option<(usize, Value)>, EvalError> {
+        let result = match self
+            .evaluator
+            .eval_stmts(&self.database, &self.type_checker, stmts)?
+        {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        self.results.push(result.clone());
+
+        Ok(Some((self.results.len(), result)))
+    }
+
+    pub(crate) fn set_overflow_mode(&mut self, overflow_mode: OverflowMode) {
+        self.evaluator.set_overflow_mode(overflow_mode);
+    }
+
+    /// Records `input` as having been entered successfully so it is included by `:save`.
+    pub(crate) fn push_history(&mut self, input: &str) {
+        self.history.push_str(input);
+
+        if !input.ends_with('\n') {
+            self.history.push('\n');
+        }
+    }
+
+    pub(crate) fn history(&self) -> &str {
+        &self.history
+    }
+
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigint::BigInt;
+    use eval::Int;
+    use hir::IntTy;
+    use text_size::TextRange;
+
+    fn check(session: &mut Session, input: &str) -> Result<Vec<Stmt>, Vec<Diagnostic>> {
+        let root = ast::Root::cast(parser::parse(input).syntax()).unwrap();
+        session.check(root)
+    }
+
+    fn int(value: i128) -> Value {
+        Value::BigInt(BigInt::from(value))
+    }
+
+    fn eval(session: &mut Session, input: &str) -> Result<Option<(usize, Value)>, EvalError> {
+        let stmts = check(session, input).unwrap();
+        session.eval(&stmts)
+    }
+
+    #[test]
+    fn bindings_persist_across_lines() {
+        let mut session = Session::default();
+
+        assert_eq!(eval(&mut session, "let a = 1"), Ok(None));
+        assert_eq!(eval(&mut session, "a + 1"), Ok(Some((1, int(2)))),);
+    }
+
+    #[test]
+    fn types_persist_across_lines() {
+        let mut session = Session::default();
+
+        eval(&mut session, "let a = true").unwrap();
+
+        assert_eq!(check(&mut session, "a + 1").unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn bindings_from_line_with_errors_are_forgotten() {
+        let mut session = Session::default();
+
+        assert_eq!(
+            check(&mut session, "let a = 1\nlet b = a + true")
+                .unwrap_err()
+                .len(),
+            1
+        );
+        assert_eq!(check(&mut session, "b").unwrap_err().len(), 1);
+
+        assert_eq!(
+            check(&mut session, "let c = 1\nlet d = e")
+                .unwrap_err()
+                .len(),
+            1
+        );
+        assert_eq!(check(&mut session, "c").unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn types_learnt_from_line_with_errors_are_forgotten() {
+        let mut session = Session::default();
+
+        eval(&mut session, "fn id(x) { x }\nlet f = id").unwrap();
+        assert_eq!(check(&mut session, "f(true) + 1").unwrap_err().len(), 1);
+
+        assert_eq!(eval(&mut session, "f(1) + 1"), Ok(Some((1, int(2)))));
+    }
+
+    #[test]
+    fn inspected_input_refers_to_earlier_bindings_but_defines_none() {
+        let mut session = Session::default();
+
+        eval(&mut session, "let a = 1").unwrap();
+
+        let root = ast::Root::cast(parser::parse("let b = a").syntax()).unwrap();
+        let (stmts, exprs) = session.lower_to_inspect(root);
+
+        assert_eq!(stmts.len(), 1);
+        assert!(matches!(exprs[..], [(_, Expr::VariableRef { .. })]));
+        assert_eq!(check(&mut session, "b").unwrap_err().len(), 1);
+    }
+
+    #[test]
+    fn results_are_numbered() {
+        let mut session = Session::default();
+
+        assert_eq!(eval(&mut session, "1"), Ok(Some((1, int(1)))));
+        assert_eq!(eval(&mut session, "2"), Ok(Some((2, int(2)))));
+    }
+
+    #[test]
+    fn history_is_newline_terminated() {
+        let mut session = Session::default();
+
+        session.push_history("let a = 1");
+        session.push_history("a\n");
+
+        assert_eq!(session.history(), "let a = 1\na\n");
+    }
+
+    #[test]
+    fn reset_clears_bindings_and_results() {
+        let mut session = Session::default();
+
+        eval(&mut session, "let a = 1").unwrap();
+        eval(&mut session, "a").unwrap();
+        session.reset();
+
+        assert_eq!(check(&mut session, "a").unwrap_err().len(), 1);
+        assert_eq!(eval(&mut session, "5"), Ok(Some((1, int(5)))));
+    }
+
+    #[test]
+    fn overflow_mode_applies_to_later_lines() {
+        let mut session = Session::default();
+
+        eval(&mut session, "let a = 255u8").unwrap();
+        session.set_overflow_mode(OverflowMode::Wrapping);
+
+        assert_eq!(
+            eval(&mut session, "a + 1u8"),
+            Ok(Some((1, Value::Int(Int::new(0, IntTy::U8).unwrap())))),
+        );
+    }
+
+    #[test]
+    fn binding_from_failed_line_has_no_value() {
+        let mut session = Session::default();
+
+        assert_eq!(
+            eval(&mut session, "let a = 1 / 0"),
+            Err(EvalError::DivisionByZero {
+                range: Some(TextRange::new(8.into(), 13.into())),
+            }),
+        );
+        assert_eq!(
+            eval(&mut session, "a"),
+            Err(EvalError::UndefinedVariable { name: "a".into() }),
+        );
+    }
+}
@@ -0,0 +1,132 @@
+use diagnostics::Diagnostic;
+use hir::IntTy;
+use smol_str::SmolStr;
+use std::fmt;
+use text_size::TextRange;
+
+#[derive(Debug, PartialEq)]
+pub enum EvalError {
+    MissingExpr,
+    /// A literal that doesn’t fit in its type, which is only possible if type checking failed
+    LiteralOutOfRange {
+        ty: IntTy,
+    },
+    UndefinedVariable {
+        name: SmolStr,
+    },
+    AssignToImmutable {
+        name: SmolStr,
+    },
+    /// `range` is that of the expression whose result overflowed, if it came from the input
+    /// being evaluated.
+    Overflow {
+        range: Option<TextRange>,
+    },
+    DivisionByZero {
+        range: Option<TextRange>,
+    },
+    NegativeExponent {
+        range: Option<TextRange>,
+    },
+    NegativeShift {
+        range: Option<TextRange>,
+    },
+    /// A result of arithmetic on `Int`s with too many bits to compute
+    ResultTooLarge {
+        range: Option<TextRange>,
+    },
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+    ExpectedFunction,
+    WrongArgumentCount {
+        expected: usize,
+        found: usize,
+    },
+    StackOverflow,
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
+}
+
+impl EvalError {
+    /// Returns a diagnostic pointing at where the error happened, if that is known.
+    pub fn to_diagnostic(&self) -> Option<Diagnostic> {
+        match self {
+            Self::Overflow { range: Some(range) } => Some(
+                Diagnostic::error("E0010", self.to_string(), *range)
+                    .with_label("the result of this doesn’t fit in its type"),
+            ),
+            Self::DivisionByZero { range: Some(range) } => Some(
+                Diagnostic::error("E0011", self.to_string(), *range)
+                    .with_label("the right-hand side of this is zero"),
+            ),
+            Self::NegativeExponent { range: Some(range) } => Some(
+                Diagnostic::error("E0012", self.to_string(), *range)
+                    .with_label("the exponent of this is negative"),
+            ),
+            Self::NegativeShift { range: Some(range) } => Some(
+                Diagnostic::error("E0013", self.to_string(), *range)
+                    .with_label("the amount this shifts by is negative"),
+            ),
+            Self::ResultTooLarge { range: Some(range) } => Some(
+                Diagnostic::error("E0014", self.to_string(), *range).with_label(format!(
+                    "the result of this would have more than {} bits",
+                    crate::int::MAX_BITS,
+                )),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Records that the error happened while evaluating the expression at `range`.
+    pub(crate) fn at(self, range: Option<TextRange>) -> Self {
+        match self {
+            Self::Overflow { .. } => Self::Overflow { range },
+            Self::DivisionByZero { .. } => Self::DivisionByZero { range },
+            Self::NegativeExponent { .. } => Self::NegativeExponent { range },
+            Self::NegativeShift { .. } => Self::NegativeShift { range },
+            Self::ResultTooLarge { .. } => Self::ResultTooLarge { range },
+            error => error,
+        }
+    }
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingExpr => write!(f, "cannot evaluate an incomplete expression"),
+            Self::LiteralOutOfRange { ty } => write!(f, "literal out of range for ‘{}’", ty),
+            Self::UndefinedVariable { name } => write!(f, "undefined variable ‘{}’", name),
+            Self::AssignToImmutable { name } => {
+                write!(f, "cannot assign to immutable variable ‘{}’", name)
+            }
+            Self::Overflow { .. } => write!(f, "integer overflow"),
+            Self::DivisionByZero { .. } => write!(f, "attempted to divide by zero"),
+            Self::NegativeExponent { .. } => {
+                write!(f, "cannot raise an integer to a negative power")
+            }
+            Self::NegativeShift { .. } => write!(f, "cannot shift by a negative amount"),
+            Self::ResultTooLarge { .. } => write!(f, "integer is too large to compute"),
+            Self::TypeMismatch { expected, found } => {
+                write!(f, "expected {}, but found {}", expected, found)
+            }
+            Self::ExpectedFunction => write!(f, "only functions can be called"),
+            Self::WrongArgumentCount { expected, found } => write!(
+                f,
+                "function takes {} argument{} but {} {} supplied",
+                expected,
+                if *expected == 1 { "" } else { "s" },
+                found,
+                if *found == 1 { "was" } else { "were" },
+            ),
+            Self::StackOverflow => write!(
+                f,
+                "function calls were nested more than {} deep",
+                crate::MAX_CALL_DEPTH,
+            ),
+            Self::BreakOutsideLoop => write!(f, "‘break’ outside of a loop"),
+            Self::ContinueOutsideLoop => write!(f, "‘continue’ outside of a loop"),
+        }
+    }
+}
@@ -0,0 +1,379 @@
+use crate::{EvalError, Value};
+use bigint::BigInt;
+use hir::{BinaryOp, IntTy};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// How many bits the result of multiplying, raising or shifting an `Int` can have. A result known
+/// to need more is refused before computing it, since that would take too long.
+pub(crate) const MAX_BITS: u64 = 1 << 18;
+
+/// Applies `op`, which must be an arithmetic, bitwise or comparison operator, to integers of the
+/// same type.
+pub(crate) fn binary(
+    op: &BinaryOp,
+    lhs: &Value,
+    rhs: &Value,
+    overflow_mode: OverflowMode,
+) -> Result<Value, EvalError> {
+    match (lhs, rhs) {
+        (Value::Int(lhs), Value::Int(rhs)) => Int::binary(op, *lhs, *rhs, overflow_mode),
+        (Value::BigInt(lhs), Value::BigInt(rhs)) => big_binary(op, lhs, rhs),
+        (Value::Int(_) | Value::BigInt(_), Value::Int(_) | Value::BigInt(_)) => {
+            Err(EvalError::TypeMismatch {
+                expected: lhs.type_name(),
+                found: rhs.type_name(),
+            })
+        }
+        (Value::Int(_) | Value::BigInt(_), value) | (value, _) => Err(expected_integer(value)),
+    }
+}
+
+pub(crate) fn neg(value: Value, overflow_mode: OverflowMode) -> Result<Value, EvalError> {
+    match value {
+        Value::Int(int) => int.neg(overflow_mode).map(Value::Int),
+        Value::BigInt(n) => Ok(Value::BigInt(-n)),
+        value => Err(expected_integer(&value)),
+    }
+}
+
+pub(crate) fn bit_not(value: Value) -> Result<Value, EvalError> {
+    match value {
+        Value::Int(int) => Ok(Value::Int(int.bit_not())),
+        Value::BigInt(n) => Ok(Value::BigInt(!&n)),
+        value => Err(expected_integer(&value)),
+    }
+}
+
+fn expected_integer(found: &Value) -> EvalError {
+    EvalError::TypeMismatch {
+        expected: "{integer}",
+        found: found.type_name(),
+    }
+}
+
+/// Applies `op` to two values of the language’s `Int` type, which can be arbitrarily large and so
+/// never overflow.
+fn big_binary(op: &BinaryOp, lhs: &BigInt, rhs: &BigInt) -> Result<Value, EvalError> {
+    let n = match op {
+        BinaryOp::Add => lhs + rhs,
+        BinaryOp::Sub => lhs - rhs,
+        BinaryOp::Mul => {
+            check_bits(lhs.bits() + rhs.bits())?;
+            lhs * rhs
+        }
+        BinaryOp::Div | BinaryOp::Rem if rhs.is_zero() => {
+            return Err(EvalError::DivisionByZero { range: None })
+        }
+        BinaryOp::Div => lhs / rhs,
+        BinaryOp::Rem => lhs % rhs,
+        BinaryOp::Pow => {
+            if rhs.is_negative() {
+                return Err(EvalError::NegativeExponent { range: None });
+            }
+
+            match rhs
+                .to_i128()
+                .and_then(|exponent| u32::try_from(exponent).ok())
+            {
+                Some(exponent) => {
+                    // Each multiplication by the base adds at least one bit fewer than it has.
+                    let min_bits = lhs.bits().saturating_sub(1) * u64::from(exponent);
+                    check_bits(min_bits)?;
+
+                    lhs.pow(exponent)
+                }
+                // Only 0, 1 and -1 can be raised to such a large power without running out of
+                // memory, and their powers only depend on whether the exponent is even.
+                None if lhs.to_i128().is_some_and(|base| base.abs() <= 1) => {
+                    let exponent_is_even = (rhs & &BigInt::from(1)).is_zero();
+                    lhs.pow(if exponent_is_even { 2 } else { 1 })
+                }
+                None => return Err(EvalError::ResultTooLarge { range: None }),
+            }
+        }
+        BinaryOp::BitAnd => lhs & rhs,
+        BinaryOp::BitOr => lhs | rhs,
+        BinaryOp::BitXor => lhs ^ rhs,
+        BinaryOp::Shl | BinaryOp::Shr => {
+            if rhs.is_negative() {
+                return Err(EvalError::NegativeShift { range: None });
+            }
+
+            let amount = rhs.to_i128().and_then(|amount| u32::try_from(amount).ok());
+
+            match (op, amount) {
+                (BinaryOp::Shl, _) if lhs.is_zero() => BigInt::zero(),
+                (BinaryOp::Shl, Some(amount)) => {
+                    check_bits(lhs.bits() + u64::from(amount))?;
+                    lhs << amount
+                }
+                (BinaryOp::Shl, None) => return Err(EvalError::ResultTooLarge { range: None }),
+                // Shifting right by this much leaves only the sign.
+                (_, amount) => lhs >> amount.unwrap_or(u32::MAX),
+            }
+        }
+        BinaryOp::Less => return Ok(Value::Bool(lhs < rhs)),
+        BinaryOp::LessEq => return Ok(Value::Bool(lhs <= rhs)),
+        BinaryOp::Greater => return Ok(Value::Bool(lhs > rhs)),
+        BinaryOp::GreaterEq => return Ok(Value::Bool(lhs >= rhs)),
+        BinaryOp::Eq | BinaryOp::NotEq | BinaryOp::And | BinaryOp::Or => unreachable!(),
+    };
+
+    Ok(Value::BigInt(n))
+}
+
+fn check_bits(bits: u64) -> Result<(), EvalError> {
+    if bits > MAX_BITS {
+        Err(EvalError::ResultTooLarge { range: None })
+    } else {
+        Ok(())
+    }
+}
+
+/// An integer of one of the fixed-width types. Arithmetic is done on `i128`s, which can hold
+/// every value of every fixed-width type, but only the low 64 bits are stored so that values stay
+/// small.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Int {
+    bits: u64,
+    ty: IntTy,
+}
+
+impl Int {
+    /// Returns `None` if `value` is out of `ty`’s range.
+    pub fn new(value: i128, ty: IntTy) -> Option<Self> {
+        if ty.contains(value) {
+            Some(Self {
+                bits: value as u64,
+                ty,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn value(self) -> i128 {
+        if self.ty.is_signed() {
+            i128::from(self.bits as i64)
+        } else {
+            i128::from(self.bits)
+        }
+    }
+
+    pub fn ty(self) -> IntTy {
+        self.ty
+    }
+
+    fn neg(self, overflow_mode: OverflowMode) -> Result<Self, EvalError> {
+        Exact::Value(-self.value()).fit(self.ty, overflow_mode)
+    }
+
+    /// Flips every bit within the width of the integer’s type.
+    fn bit_not(self) -> Self {
+        let value = if self.ty.is_signed() {
+            !self.value()
+        } else {
+            self.ty.max() - self.value()
+        };
+
+        Self {
+            bits: value as u64,
+            ty: self.ty,
+        }
+    }
+
+    /// Like the `binary` function, but for fixed-width integers.
+    fn binary(
+        op: &BinaryOp,
+        lhs: Self,
+        rhs: Self,
+        overflow_mode: OverflowMode,
+    ) -> Result<Value, EvalError> {
+        if lhs.ty != rhs.ty {
+            return Err(EvalError::TypeMismatch {
+                expected: lhs.ty.name(),
+                found: rhs.ty.name(),
+            });
+        }
+
+        let ty = lhs.ty;
+        let (lhs, rhs) = (lhs.value(), rhs.value());
+
+        // Since the operands are at most 64 bits wide, only multiplication and exponentiation
+        // can overflow an `i128`.
+        let exact = match op {
+            BinaryOp::Add => Exact::Value(lhs + rhs),
+            BinaryOp::Sub => Exact::Value(lhs - rhs),
+            BinaryOp::Mul => {
+                let (low_bits, overflowed) = lhs.overflowing_mul(rhs);
+
+                if overflowed {
+                    Exact::Overflowed {
+                        low_bits,
+                        negative: (lhs < 0) != (rhs < 0),
+                    }
+                } else {
+                    Exact::Value(low_bits)
+                }
+            }
+            BinaryOp::Div | BinaryOp::Rem if rhs == 0 => {
+                return Err(EvalError::DivisionByZero { range: None })
+            }
+            BinaryOp::Div => Exact::Value(lhs / rhs),
+            BinaryOp::Rem => Exact::Value(lhs % rhs),
+            BinaryOp::Pow => pow(lhs, rhs)?,
+            BinaryOp::BitAnd => Exact::Value(lhs & rhs),
+            BinaryOp::BitOr => Exact::Value(lhs | rhs),
+            BinaryOp::BitXor => Exact::Value(lhs ^ rhs),
+            // Bits shifted past the width of the type are lost rather than overflowing, but the
+            // shift itself has to be narrower than the type.
+            BinaryOp::Shl | BinaryOp::Shr => {
+                let amount = match u32::try_from(rhs) {
+                    Ok(amount) if amount < ty.bits() => amount,
+                    _ => match overflow_mode {
+                        OverflowMode::Checked => return Err(EvalError::Overflow { range: None }),
+                        OverflowMode::Wrapping => (rhs & i128::from(ty.bits() - 1)) as u32,
+                        OverflowMode::Saturating => ty.bits(),
+                    },
+                };
+
+                let value = if *op == BinaryOp::Shl {
+                    wrap(lhs << amount, ty)
+                } else {
+                    lhs >> amount
+                };
+
+                Exact::Value(value)
+            }
+            BinaryOp::Less => return Ok(Value::Bool(lhs < rhs)),
+            BinaryOp::LessEq => return Ok(Value::Bool(lhs <= rhs)),
+            BinaryOp::Greater => return Ok(Value::Bool(lhs > rhs)),
+            BinaryOp::GreaterEq => return Ok(Value::Bool(lhs >= rhs)),
+            BinaryOp::Eq | BinaryOp::NotEq | BinaryOp::And | BinaryOp::Or => unreachable!(),
+        };
+
+        Ok(Value::Int(exact.fit(ty, overflow_mode)?))
+    }
+}
+
+/// What happens when the result of arithmetic on fixed-width integers doesn’t fit in its type.
+/// Shifting by at least the width of the type counts as overflowing too.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OverflowMode {
+    /// Evaluation stops with an error.
+    #[default]
+    Checked,
+    /// Only the bits of the result that fit in the type are kept, so it wraps around from the
+    /// largest value to the smallest and vice versa. Shifts use the amount modulo the width of
+    /// the type.
+    Wrapping,
+    /// The result is clamped to the type’s smallest or largest value. Shifting by too much
+    /// shifts out every bit.
+    Saturating,
+}
+
+impl OverflowMode {
+    /// Looks up the mode called `name`, which is ‘checked’, ‘wrapping’ or ‘saturating’.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let mode = match name {
+            "checked" => Self::Checked,
+            "wrapping" => Self::Wrapping,
+            "saturating" => Self::Saturating,
+            _ => return None,
+        };
+
+        Some(mode)
+    }
+}
+
+/// The mathematically exact result of an operation, before it has been fit into its type.
+enum Exact {
+    Value(i128),
+    /// A result too large for an `i128`, of which only the low 128 bits are known. That is still
+    /// enough to wrap it, since every type is narrower.
+    Overflowed {
+        low_bits: i128,
+        negative: bool,
+    },
+}
+
+impl Exact {
+    fn fit(self, ty: IntTy, overflow_mode: OverflowMode) -> Result<Int, EvalError> {
+        let value = match (self, overflow_mode) {
+            (Self::Value(value), _) if ty.contains(value) => value,
+            (_, OverflowMode::Checked) => return Err(EvalError::Overflow { range: None }),
+            (Self::Value(value), OverflowMode::Wrapping)
+            | (
+                Self::Overflowed {
+                    low_bits: value, ..
+                },
+                OverflowMode::Wrapping,
+            ) => wrap(value, ty),
+            (Self::Value(value), OverflowMode::Saturating) => value.clamp(ty.min(), ty.max()),
+            (Self::Overflowed { negative, .. }, OverflowMode::Saturating) => {
+                if negative {
+                    ty.min()
+                } else {
+                    ty.max()
+                }
+            }
+        };
+
+        Ok(Int::new(value, ty).unwrap())
+    }
+}
+
+impl fmt::Display for Int {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+/// Raises `base` to the power of `exponent`.
+fn pow(base: i128, exponent: i128) -> Result<Exact, EvalError> {
+    if exponent < 0 {
+        return Err(EvalError::NegativeExponent { range: None });
+    }
+
+    // Exponentiation by squaring, keeping track of whether any step overflowed.
+    let (mut result, mut overflowed) = (1_i128, false);
+    let (mut square, mut remaining) = (base, exponent);
+
+    while remaining > 0 {
+        if remaining % 2 == 1 {
+            let (product, product_overflowed) = result.overflowing_mul(square);
+            result = product;
+            overflowed |= product_overflowed;
+        }
+
+        remaining /= 2;
+
+        if remaining > 0 {
+            let (product, product_overflowed) = square.overflowing_mul(square);
+            square = product;
+            overflowed |= product_overflowed;
+        }
+    }
+
+    if overflowed {
+        Ok(Exact::Overflowed {
+            low_bits: result,
+            negative: base < 0 && exponent % 2 == 1,
+        })
+    } else {
+        Ok(Exact::Value(result))
+    }
+}
+
+/// Keeps only the bits of `value` that fit in `ty`, as if it had been computed in a type of that
+/// width.
+fn wrap(value: i128, ty: IntTy) -> i128 {
+    let value = value & ((1 << ty.bits()) - 1);
+
+    if value > ty.max() {
+        value - (1 << ty.bits())
+    } else {
+        value
+    }
+}
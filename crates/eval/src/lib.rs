@@ -0,0 +1,1230 @@
+mod eval_error;
+mod int;
+mod value;
+
+pub use eval_error::EvalError;
+pub use int::{Int, OverflowMode};
+pub use value::Value;
+
+use hir::{BinaryOp, BindingIdx, BindingKind, Database, Expr, ExprIdx, FnIdx, Stmt, UnaryOp};
+use std::collections::HashMap;
+use std::mem;
+use typeck::{Ty, TypeChecker};
+
+/// How deeply function calls can be nested before evaluation is abandoned.
+const MAX_CALL_DEPTH: usize = 256;
+
+type Scope = HashMap<BindingIdx, Value>;
+
+#[derive(Debug, Default)]
+pub struct Evaluator {
+    globals: Scope,
+    /// The local scopes of the code currently being evaluated, innermost last.
+    scopes: Vec<Scope>,
+    /// The local scopes of each caller whose evaluation is suspended until a call returns.
+    call_stack: Vec<Vec<Scope>>,
+    /// Every function whose definition has been reached. Unlike variables, functions stay
+    /// visible inside calls, since a function can call itself and the functions defined next to
+    /// it, and their values never change.
+    fns: HashMap<BindingIdx, FnIdx>,
+    overflow_mode: OverflowMode,
+}
+
+/// The reasons evaluation can stop part-way through an expression. `break` and `continue` unwind
+/// up to the innermost loop, which is why they are treated like errors internally.
+#[derive(Debug)]
+enum Unwind {
+    Error(EvalError),
+    Break,
+    Continue,
+}
+
+impl Unwind {
+    /// Converts `break` and `continue` that weren’t caught by a loop into errors.
+    fn into_error(self) -> EvalError {
+        match self {
+            Self::Error(error) => error,
+            Self::Break => EvalError::BreakOutsideLoop,
+            Self::Continue => EvalError::ContinueOutsideLoop,
+        }
+    }
+}
+
+impl From<EvalError> for Unwind {
+    fn from(error: EvalError) -> Self {
+        Self::Error(error)
+    }
+}
+
+impl Evaluator {
+    pub fn new(overflow_mode: OverflowMode) -> Self {
+        Self {
+            overflow_mode,
+            ..Self::default()
+        }
+    }
+
+    /// Changes the overflow mode used from now on, keeping the values of every binding.
+    pub fn set_overflow_mode(&mut self, overflow_mode: OverflowMode) {
+        self.overflow_mode = overflow_mode;
+    }
+
+    /// Evaluates each statement in turn, returning the value of the last one if it was an
+    /// expression statement.
+    pub fn eval_stmts(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        stmts: &[Stmt],
+    ) -> Result<Option<Value>, EvalError> {
+        self.eval_stmt_list(db, types, stmts)
+            .map_err(Unwind::into_error)
+    }
+
+    fn eval_stmt_list(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        stmts: &[Stmt],
+    ) -> Result<Option<Value>, Unwind> {
+        // Functions can be called before the point they are defined at.
+        for stmt in stmts {
+            if let Stmt::FnDef { binding, func } = stmt {
+                self.fns.insert(*binding, *func);
+            }
+        }
+
+        let mut result = None;
+
+        for stmt in stmts {
+            result = self.eval_stmt(db, types, stmt)?;
+        }
+
+        Ok(result)
+    }
+
+    fn eval_stmt(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        stmt: &Stmt,
+    ) -> Result<Option<Value>, Unwind> {
+        match stmt {
+            Stmt::Break => Err(Unwind::Break),
+            Stmt::Continue => Err(Unwind::Continue),
+            Stmt::FnDef { .. } => Ok(None),
+            Stmt::VariableDef { binding, value, .. } => {
+                let value = self.eval_expr(db, types, *value)?;
+                self.define(*binding, value);
+
+                Ok(None)
+            }
+            Stmt::While { condition, body } => {
+                while self.eval_bool(db, types, *condition)? {
+                    match self.eval_expr(db, types, *body) {
+                        Ok(_) | Err(Unwind::Continue) => {}
+                        Err(Unwind::Break) => break,
+                        Err(error) => return Err(error),
+                    }
+                }
+
+                Ok(None)
+            }
+            Stmt::Expr(expr) => self.eval_expr(db, types, *expr).map(Some),
+        }
+    }
+
+    fn eval_expr(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        expr: ExprIdx,
+    ) -> Result<Value, Unwind> {
+        let value = match &db[expr] {
+            Expr::Missing => return Err(EvalError::MissingExpr.into()),
+            Expr::Assign { binding, op, value } => {
+                self.eval_assign(db, types, expr, *binding, op.as_ref(), *value)?;
+                Value::Unit
+            }
+            Expr::Binary { op, lhs, rhs } => self.eval_binary(db, types, expr, op, *lhs, *rhs)?,
+            Expr::Block { stmts, tail } => self.eval_block(db, types, stmts, *tail)?,
+            Expr::Bool { value } => Value::Bool(*value),
+            Expr::Call { callee, args } => self.eval_call(db, types, *callee, args)?,
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if self.eval_bool(db, types, *condition)? {
+                    self.eval_expr(db, types, *then_branch)?
+                } else if let Some(else_branch) = else_branch {
+                    self.eval_expr(db, types, *else_branch)?
+                } else {
+                    Value::Unit
+                }
+            }
+            Expr::Literal { .. } => eval_literal(db, types, expr, false)?,
+            Expr::Unary { op, expr: operand } => self.eval_unary(db, types, expr, op, *operand)?,
+            Expr::VariableRef { binding } => {
+                self.lookup(*binding)
+                    .ok_or_else(|| EvalError::UndefinedVariable {
+                        name: db[*binding].name.clone(),
+                    })?
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn eval_assign(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        assign: ExprIdx,
+        binding: BindingIdx,
+        op: Option<&BinaryOp>,
+        value: ExprIdx,
+    ) -> Result<(), Unwind> {
+        let name = &db[binding].name;
+
+        if db[binding].kind != (BindingKind::Variable { mutable: true }) {
+            return Err(EvalError::AssignToImmutable { name: name.clone() }.into());
+        }
+
+        let value = self.eval_expr(db, types, value)?;
+        let overflow_mode = self.overflow_mode;
+
+        let variable = self
+            .lookup_mut(binding)
+            .ok_or_else(|| EvalError::UndefinedVariable { name: name.clone() })?;
+
+        *variable = match op {
+            None => value,
+            Some(op) => int::binary(op, variable, &value, overflow_mode)
+                .map_err(|error| error.at(db.source_map().expr_range(assign)))?,
+        };
+
+        Ok(())
+    }
+
+    fn eval_binary(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        binary: ExprIdx,
+        op: &BinaryOp,
+        lhs: ExprIdx,
+        rhs: ExprIdx,
+    ) -> Result<Value, Unwind> {
+        match op {
+            // `&&` and `||` short-circuit, so the right-hand side is only evaluated if needed.
+            BinaryOp::And => Ok(Value::Bool(
+                self.eval_bool(db, types, lhs)? && self.eval_bool(db, types, rhs)?,
+            )),
+            BinaryOp::Or => Ok(Value::Bool(
+                self.eval_bool(db, types, lhs)? || self.eval_bool(db, types, rhs)?,
+            )),
+            BinaryOp::Eq | BinaryOp::NotEq => {
+                let lhs = self.eval_expr(db, types, lhs)?;
+                let rhs = self.eval_expr(db, types, rhs)?;
+
+                if mem::discriminant(&lhs) != mem::discriminant(&rhs) {
+                    return Err(EvalError::TypeMismatch {
+                        expected: lhs.type_name(),
+                        found: rhs.type_name(),
+                    }
+                    .into());
+                }
+
+                Ok(Value::Bool((lhs == rhs) == (*op == BinaryOp::Eq)))
+            }
+            _ => {
+                let lhs = self.eval_expr(db, types, lhs)?;
+                let rhs = self.eval_expr(db, types, rhs)?;
+
+                int::binary(op, &lhs, &rhs, self.overflow_mode)
+                    .map_err(|error| error.at(db.source_map().expr_range(binary)).into())
+            }
+        }
+    }
+
+    fn eval_unary(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        unary: ExprIdx,
+        op: &UnaryOp,
+        operand: ExprIdx,
+    ) -> Result<Value, Unwind> {
+        let value = match op {
+            UnaryOp::Neg => match db[operand] {
+                Expr::Literal { .. } => eval_literal(db, types, operand, true)?,
+                _ => {
+                    let value = self.eval_expr(db, types, operand)?;
+
+                    int::neg(value, self.overflow_mode)
+                        .map_err(|error| error.at(db.source_map().expr_range(unary)))?
+                }
+            },
+            UnaryOp::Not => Value::Bool(!self.eval_bool(db, types, operand)?),
+            UnaryOp::BitNot => int::bit_not(self.eval_expr(db, types, operand)?)?,
+        };
+
+        Ok(value)
+    }
+
+    fn eval_bool(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        expr: ExprIdx,
+    ) -> Result<bool, Unwind> {
+        match self.eval_expr(db, types, expr)? {
+            Value::Bool(b) => Ok(b),
+            value => Err(EvalError::TypeMismatch {
+                expected: "Bool",
+                found: value.type_name(),
+            }
+            .into()),
+        }
+    }
+
+    fn eval_block(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        stmts: &[Stmt],
+        tail: Option<ExprIdx>,
+    ) -> Result<Value, Unwind> {
+        self.scopes.push(Scope::new());
+
+        let result = self
+            .eval_stmt_list(db, types, stmts)
+            .and_then(|_| match tail {
+                Some(tail) => self.eval_expr(db, types, tail),
+                None => Ok(Value::Unit),
+            });
+
+        // The scope has to be popped even if evaluation is cut short so that later statements
+        // don’t see the block’s variables.
+        self.scopes.pop();
+
+        result
+    }
+
+    fn eval_call(
+        &mut self,
+        db: &Database,
+        types: &TypeChecker,
+        callee: ExprIdx,
+        args: &[ExprIdx],
+    ) -> Result<Value, Unwind> {
+        let func = match self.eval_expr(db, types, callee)? {
+            Value::Function(func) => &db[func],
+            _ => return Err(EvalError::ExpectedFunction.into()),
+        };
+
+        if args.len() != func.params.len() {
+            return Err(EvalError::WrongArgumentCount {
+                expected: func.params.len(),
+                found: args.len(),
+            }
+            .into());
+        }
+
+        if self.call_stack.len() == MAX_CALL_DEPTH {
+            return Err(EvalError::StackOverflow.into());
+        }
+
+        let mut params = Scope::new();
+
+        for (param, arg) in func.params.iter().zip(args) {
+            let arg = self.eval_expr(db, types, *arg)?;
+            params.insert(param.binding, arg);
+        }
+
+        // The caller’s scopes have to be restored even if evaluating the body fails so that later
+        // statements aren’t evaluated inside the function.
+        let caller_scopes = mem::replace(&mut self.scopes, vec![params]);
+        self.call_stack.push(caller_scopes);
+
+        let result = self.eval_expr(db, types, func.body);
+
+        self.scopes = self.call_stack.pop().unwrap();
+
+        // A `break` or `continue` can’t affect a loop in the caller.
+        result.map_err(|unwind| Unwind::Error(unwind.into_error()))
+    }
+
+    fn define(&mut self, binding: BindingIdx, value: Value) {
+        let scope = self.scopes.last_mut().unwrap_or(&mut self.globals);
+        scope.insert(binding, value);
+    }
+
+    /// Looks up the value of `binding` from the innermost scope outwards, falling back to global
+    /// variables and then to functions. Variables belonging to callers are not visible, which
+    /// matters because a recursive call defines the same parameters as its caller.
+    fn lookup(&self, binding: BindingIdx) -> Option<Value> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(&binding))
+            .or_else(|| self.globals.get(&binding))
+            .cloned()
+            .or_else(|| self.fns.get(&binding).copied().map(Value::Function))
+    }
+
+    fn lookup_mut(&mut self, binding: BindingIdx) -> Option<&mut Value> {
+        let globals = &mut self.globals;
+
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.get_mut(&binding))
+            .or_else(move || globals.get_mut(&binding))
+    }
+}
+
+/// Evaluates `literal`, which has the type the type checker inferred for it, negating it first if
+/// `negated` is true.
+fn eval_literal(
+    db: &Database,
+    types: &TypeChecker,
+    literal: ExprIdx,
+    negated: bool,
+) -> Result<Value, EvalError> {
+    let (n, suffix) = match &db[literal] {
+        Expr::Literal { n, suffix } => (n, *suffix),
+        _ => unreachable!(),
+    };
+
+    // Literals always have an integer type once they have been checked.
+    let ty = match (types.type_of(literal), suffix) {
+        (Ty::Int(ty), _) | (_, Some(ty)) => ty,
+        _ => return Ok(Value::BigInt(if negated { -n } else { n.clone() })),
+    };
+
+    ty.literal_value(n, negated)
+        .and_then(|n| Int::new(n, ty))
+        .map(Value::Int)
+        .ok_or(EvalError::LiteralOutOfRange { ty })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigint::BigInt;
+    use diagnostics::Diagnostic;
+    use hir::IntTy;
+    use text_size::TextRange;
+
+    fn check(input: &str, expected: Result<Option<Value>, EvalError>) {
+        check_with_overflow_mode(input, OverflowMode::Checked, expected);
+    }
+
+    fn check_with_overflow_mode(
+        input: &str,
+        overflow_mode: OverflowMode,
+        expected: Result<Option<Value>, EvalError>,
+    ) {
+        let parse = parser::parse(input);
+        let root = ast::Root::cast(parse.syntax()).unwrap();
+        let (db, stmts, _) = hir::lower(root);
+        let (types, _) = typeck::check(&db, &stmts);
+
+        assert_eq!(
+            Evaluator::new(overflow_mode).eval_stmts(&db, &types, &stmts),
+            expected
+        );
+    }
+
+    fn overflow(start: u32, end: u32) -> EvalError {
+        EvalError::Overflow {
+            range: Some(TextRange::new(start.into(), end.into())),
+        }
+    }
+
+    fn division_by_zero(start: u32, end: u32) -> EvalError {
+        EvalError::DivisionByZero {
+            range: Some(TextRange::new(start.into(), end.into())),
+        }
+    }
+
+    fn negative_exponent(start: u32, end: u32) -> EvalError {
+        EvalError::NegativeExponent {
+            range: Some(TextRange::new(start.into(), end.into())),
+        }
+    }
+
+    fn negative_shift(start: u32, end: u32) -> EvalError {
+        EvalError::NegativeShift {
+            range: Some(TextRange::new(start.into(), end.into())),
+        }
+    }
+
+    fn too_large(start: u32, end: u32) -> EvalError {
+        EvalError::ResultTooLarge {
+            range: Some(TextRange::new(start.into(), end.into())),
+        }
+    }
+
+    fn int(value: i128) -> Value {
+        Value::BigInt(BigInt::from(value))
+    }
+
+    fn int_of_ty(value: i128, ty: IntTy) -> Value {
+        Value::Int(Int::new(value, ty).unwrap())
+    }
+
+    #[test]
+    fn eval_nothing() {
+        check("", Ok(None));
+    }
+
+    #[test]
+    fn eval_literal() {
+        check("123", Ok(Some(int(123))));
+    }
+
+    #[test]
+    fn eval_binary_expr() {
+        check("1 + 2 * 3 - 4 / 2", Ok(Some(int(5))));
+    }
+
+    #[test]
+    fn eval_paren_expr() {
+        check("(1 + 2) * 3", Ok(Some(int(9))));
+    }
+
+    #[test]
+    fn eval_negation_of_zero() {
+        check("-0", Ok(Some(int(0))));
+    }
+
+    #[test]
+    fn eval_negation_of_non_zero_number() {
+        check("-5", Ok(Some(int(-5))));
+    }
+
+    #[test]
+    fn eval_negation_of_smallest_value() {
+        check("-128i8", Ok(Some(int_of_ty(-128, IntTy::I8))));
+        check("fn neg(x) { -x }\nneg(-128i8)", Err(overflow(12, 14)));
+    }
+
+    #[test]
+    fn eval_variable_def() {
+        check("let a = 5", Ok(None));
+    }
+
+    #[test]
+    fn eval_variable_ref() {
+        check("let a = 5\nlet b = a * 2\nb + a", Ok(Some(int(15))));
+    }
+
+    #[test]
+    fn eval_assign() {
+        check("let mut a = 1\na = a + 1\na", Ok(Some(int(2))));
+    }
+
+    #[test]
+    fn eval_assign_is_unit() {
+        check("let mut a = 1\na = 2", Ok(Some(Value::Unit)));
+    }
+
+    #[test]
+    fn eval_compound_assign() {
+        check(
+            "let mut a = 10\na -= 4\na *= 3\na /= 2\na",
+            Ok(Some(int(9))),
+        );
+    }
+
+    #[test]
+    fn eval_assign_is_right_associative() {
+        check(
+            "let mut a = 1\nlet mut b = 2\na = b = 3\na",
+            Ok(Some(Value::Unit)),
+        );
+    }
+
+    #[test]
+    fn eval_assign_to_immutable_variable() {
+        check(
+            "let a = 1\na = 2",
+            Err(EvalError::AssignToImmutable { name: "a".into() }),
+        );
+    }
+
+    #[test]
+    fn eval_assign_to_param() {
+        check(
+            "fn f(x) { x = 1 }\nf(0)",
+            Err(EvalError::AssignToImmutable { name: "x".into() }),
+        );
+    }
+
+    #[test]
+    fn eval_assign_to_outer_variable_from_block() {
+        check("let mut a = 1\n{ a = 2 }\na", Ok(Some(int(2))));
+    }
+
+    #[test]
+    fn eval_assign_to_shadowing_variable() {
+        check(
+            "let mut a = 1\n{ let mut a = 5\na = 2 }\na",
+            Ok(Some(int(1))),
+        );
+    }
+
+    #[test]
+    fn eval_compound_assign_to_boolean() {
+        check(
+            "let mut a = true\na += 1",
+            Err(EvalError::TypeMismatch {
+                expected: "{integer}",
+                found: "Bool",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_overflowing_compound_assign() {
+        check("let mut a = 0u8\na -= 1", Err(overflow(16, 22)));
+    }
+
+    #[test]
+    fn eval_missing_expr() {
+        check("1 +", Err(EvalError::MissingExpr));
+    }
+
+    #[test]
+    fn eval_literal_larger_than_every_fixed_width_type() {
+        check(
+            "99999999999999999999",
+            Ok(Some(Value::BigInt("99999999999999999999".parse().unwrap()))),
+        );
+    }
+
+    #[test]
+    fn eval_overflowing_addition() {
+        check("9223372036854775807i64 + 1", Err(overflow(0, 26)));
+        check("255u8 + 1", Err(overflow(0, 9)));
+    }
+
+    #[test]
+    fn eval_literal_with_inferred_type() {
+        check(
+            "let a: u8 = 200\na + 50",
+            Ok(Some(int_of_ty(250, IntTy::U8))),
+        );
+        check("let a: u8 = 200\na + 100", Err(overflow(16, 23)));
+    }
+
+    #[test]
+    fn eval_overflowing_signed_division() {
+        check("-9223372036854775808i64 / -1", Err(overflow(0, 28)));
+    }
+
+    #[test]
+    fn eval_division_by_zero() {
+        check("1 / 0", Err(division_by_zero(0, 5)));
+    }
+
+    #[test]
+    fn overflow_diagnostic() {
+        assert_eq!(
+            overflow(0, 9).to_diagnostic(),
+            Some(
+                Diagnostic::error(
+                    "E0010",
+                    "integer overflow",
+                    TextRange::new(0.into(), 9.into()),
+                )
+                .with_label("the result of this doesn’t fit in its type"),
+            ),
+        );
+        assert_eq!(EvalError::Overflow { range: None }.to_diagnostic(), None);
+    }
+
+    #[test]
+    fn result_too_large_diagnostic() {
+        assert_eq!(
+            too_large(0, 15).to_diagnostic(),
+            Some(
+                Diagnostic::error(
+                    "E0014",
+                    "integer is too large to compute",
+                    TextRange::new(0.into(), 15.into()),
+                )
+                .with_label("the result of this would have more than 262144 bits"),
+            ),
+        );
+    }
+
+    #[test]
+    fn eval_wrapping_arithmetic() {
+        let check = |input, value, ty| {
+            check_with_overflow_mode(
+                input,
+                OverflowMode::Wrapping,
+                Ok(Some(int_of_ty(value, ty))),
+            )
+        };
+
+        check("255u8 + 1", 0, IntTy::U8);
+        check("0u8 - 1", 255, IntTy::U8);
+        check("127i8 + 1", -128, IntTy::I8);
+        check("-128i8 / -1", -128, IntTy::I8);
+        check("fn neg(x) { -x }\nneg(-128i8)", -128, IntTy::I8);
+        check(
+            "18446744073709551615u64 * 18446744073709551615",
+            1,
+            IntTy::U64,
+        );
+        check("3i8 ** 5", -13, IntTy::I8);
+        check("2i64 ** 64", 0, IntTy::I64);
+        check("3u64 ** 5000000000", 14_825_714_590_500_431_873, IntTy::U64);
+        check("1u8 << 9", 2, IntTy::U8);
+    }
+
+    #[test]
+    fn eval_saturating_arithmetic() {
+        let check = |input, value, ty| {
+            check_with_overflow_mode(
+                input,
+                OverflowMode::Saturating,
+                Ok(Some(int_of_ty(value, ty))),
+            )
+        };
+
+        check("250u8 + 10", 255, IntTy::U8);
+        check("0u8 - 1", 0, IntTy::U8);
+        check("-100i8 - 100", -128, IntTy::I8);
+        check("fn neg(x) { -x }\nneg(-128i8)", 127, IntTy::I8);
+        check("18446744073709551615u64 * 2", u64::MAX.into(), IntTy::U64);
+        check(
+            "(-9223372036854775808i64) ** 3",
+            i64::MIN.into(),
+            IntTy::I64,
+        );
+        check("(-2i64) ** 1000", i64::MAX.into(), IntTy::I64);
+        check("255u8 << 8", 0, IntTy::U8);
+        check("-1i64 >> 64", -1, IntTy::I64);
+    }
+
+    #[test]
+    fn division_by_zero_is_an_error_in_every_overflow_mode() {
+        for overflow_mode in &[OverflowMode::Wrapping, OverflowMode::Saturating] {
+            check_with_overflow_mode("1 / 0", *overflow_mode, Err(division_by_zero(0, 5)));
+        }
+    }
+
+    #[test]
+    fn eval_fn_def() {
+        check("fn f() { 1 }", Ok(None));
+    }
+
+    #[test]
+    fn eval_call() {
+        check("fn add(x, y) { x + y }\nadd(1, 2 * 3)", Ok(Some(int(7))));
+    }
+
+    #[test]
+    fn eval_call_with_local_variables() {
+        check(
+            "fn twiceplusone(x) {\n  let y = x * 2\n  y + 1\n}\ntwiceplusone(4)",
+            Ok(Some(int(9))),
+        );
+    }
+
+    #[test]
+    fn eval_call_with_globals() {
+        check("let a = 10\nfn f(x) { x + a }\nf(1)", Ok(Some(int(11))));
+    }
+
+    #[test]
+    fn eval_call_before_definition() {
+        check("let a = f()\nfn f() { 5 }\na", Ok(Some(int(5))));
+    }
+
+    #[test]
+    fn eval_mutually_recursive_fns() {
+        check(
+            "fn even(n) { if n == 0 { true } else { odd(n - 1) } }\nfn odd(n) { if n == 0 { false } else { even(n - 1) } }\neven(10)",
+            Ok(Some(Value::Bool(true))),
+        );
+    }
+
+    #[test]
+    fn eval_call_without_result() {
+        check("fn f() { let a = 1 }\nf()", Ok(Some(Value::Unit)));
+    }
+
+    #[test]
+    fn eval_nested_calls() {
+        check("fn inc(x) { x + 1 }\ninc(inc(inc(0)))", Ok(Some(int(3))));
+    }
+
+    #[test]
+    fn eval_call_with_wrong_argument_count() {
+        check(
+            "fn f(x) { x }\nf(1, 2)",
+            Err(EvalError::WrongArgumentCount {
+                expected: 1,
+                found: 2,
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_call_of_number() {
+        check("let a = 1\na()", Err(EvalError::ExpectedFunction));
+    }
+
+    #[test]
+    fn eval_arithmetic_on_function() {
+        check(
+            "fn f() { 1 }\nf + 1",
+            Err(EvalError::TypeMismatch {
+                expected: "{integer}",
+                found: "fn(…) -> …",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_boolean_literal() {
+        check("true", Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn eval_arithmetic_on_boolean() {
+        check(
+            "1 + false",
+            Err(EvalError::TypeMismatch {
+                expected: "{integer}",
+                found: "Bool",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_while_loop() {
+        check("let n = 0\nwhile true { break }\nn", Ok(Some(int(0))));
+    }
+
+    #[test]
+    fn eval_counting_while_loop() {
+        check(
+            "let mut i = 0\nlet mut sum = 0\nwhile i < 5 { i += 1\nsum += i }\nsum",
+            Ok(Some(int(15))),
+        );
+    }
+
+    #[test]
+    fn continue_skips_rest_of_loop_body() {
+        check(
+            "let mut i = 0\nlet mut odd = 0\nwhile i < 10 {\n  i += 1\n  if i % 2 == 0 { continue }\n  odd += 1\n}\nodd",
+            Ok(Some(int(5))),
+        );
+    }
+
+    #[test]
+    fn eval_while_loop_with_false_condition() {
+        check("while false { 1 / 0 }", Ok(None));
+    }
+
+    #[test]
+    fn break_leaves_only_innermost_loop() {
+        check(
+            "fn f() { while true { while true { break }\nbreak }\n5 }\nf()",
+            Ok(Some(int(5))),
+        );
+    }
+
+    #[test]
+    fn eval_while_loop_with_non_boolean_condition() {
+        check(
+            "while 1 {}",
+            Err(EvalError::TypeMismatch {
+                expected: "Bool",
+                found: "Int",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_break_outside_loop() {
+        check("break", Err(EvalError::BreakOutsideLoop));
+    }
+
+    #[test]
+    fn eval_continue_outside_loop() {
+        check("{ continue }", Err(EvalError::ContinueOutsideLoop));
+    }
+
+    #[test]
+    fn break_does_not_escape_function() {
+        check(
+            "fn f() { break }\nwhile true { f() }",
+            Err(EvalError::BreakOutsideLoop),
+        );
+    }
+
+    #[test]
+    fn eval_remainder() {
+        check("17 % 5", Ok(Some(int(2))));
+    }
+
+    #[test]
+    fn eval_remainder_by_zero() {
+        check("1 % 0", Err(division_by_zero(0, 5)));
+    }
+
+    #[test]
+    fn eval_exponent() {
+        check("2 ** 10", Ok(Some(int(1024))));
+        check("5 ** 0", Ok(Some(int(1))));
+    }
+
+    #[test]
+    fn exponent_is_right_associative() {
+        check("2 ** 3 ** 2", Ok(Some(int(512))));
+    }
+
+    #[test]
+    fn eval_overflowing_exponent() {
+        check("2i64 ** 63", Err(overflow(0, 10)));
+        check("2u64 ** 63", Ok(Some(int_of_ty(1 << 63, IntTy::U64))));
+    }
+
+    #[test]
+    fn eval_negative_exponent() {
+        check("2 ** -1", Err(negative_exponent(0, 7)));
+    }
+
+    #[test]
+    fn eval_exponent_larger_than_u32() {
+        check("1i64 ** 5000000000", Ok(Some(int_of_ty(1, IntTy::I64))));
+        check("2i64 ** 5000000000", Err(overflow(0, 18)));
+        check("1 ** 5000000000", Ok(Some(int(1))));
+        check("(-1) ** 5000000001", Ok(Some(int(-1))));
+        check("2 ** 5000000000", Err(too_large(0, 15)));
+    }
+
+    #[test]
+    fn eval_power_with_too_many_bits() {
+        check("3 ** 4000000000", Err(too_large(0, 15)));
+        check("(-2) ** 262145", Err(too_large(0, 14)));
+        check("0 ** 4000000000", Ok(Some(int(0))));
+    }
+
+    #[test]
+    fn eval_bitwise_operators() {
+        check("12 & 10", Ok(Some(int(8))));
+        check("12 | 10", Ok(Some(int(14))));
+        check("12 ^ 10", Ok(Some(int(6))));
+        check("1 << 4", Ok(Some(int(16))));
+        check("256 >> 4", Ok(Some(int(16))));
+    }
+
+    #[test]
+    fn eval_bitmask() {
+        check("let flags = 5\nflags & 4 == 4", Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn eval_shift_by_too_much() {
+        check("1i64 << 64", Err(overflow(0, 10)));
+        check("1i64 >> 64", Err(overflow(0, 10)));
+        check("1u8 << 8", Err(overflow(0, 8)));
+        check("1i64 << -1", Err(overflow(0, 10)));
+    }
+
+    #[test]
+    fn eval_big_int_shifts() {
+        check(
+            "1 << 100",
+            Ok(Some(Value::BigInt(BigInt::from(1_u128 << 100)))),
+        );
+        check("-5 >> 1", Ok(Some(int(-3))));
+        check("-5 >> 100000000000", Ok(Some(int(-1))));
+        check("0 << 100000000000", Ok(Some(int(0))));
+        check("1 << 100000000000", Err(too_large(0, 17)));
+        check("1 << -1", Err(negative_shift(0, 7)));
+    }
+
+    #[test]
+    fn eval_repeated_squaring_with_too_many_bits() {
+        check(
+            "let mut x = 2\nwhile true { x = x * x }",
+            Err(too_large(31, 36)),
+        );
+    }
+
+    #[test]
+    fn eval_shift_with_too_many_bits() {
+        check("1 << 4000000000", Err(too_large(0, 15)));
+        check("0 << 4000000000", Ok(Some(int(0))));
+    }
+
+    #[test]
+    fn eval_big_int_arithmetic_never_overflows() {
+        check(
+            "9223372036854775807 * 9223372036854775807 * 10",
+            Ok(Some(Value::BigInt(
+                "850705917302346158473969077842325012490".parse().unwrap(),
+            ))),
+        );
+        check(
+            "2 ** 200 / 3 ** 50",
+            Ok(Some(Value::BigInt(
+                "2238393297946874000179418290327143433".parse().unwrap(),
+            ))),
+        );
+        check("-7 % 3", Ok(Some(int(-1))));
+        check("~(2 ** 100) & 7", Ok(Some(int(7))));
+        check("3 ** 80 > 2 ** 126", Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn eval_big_int_division_by_zero() {
+        check("let a: Int = 5\na % 0", Err(division_by_zero(15, 20)));
+    }
+
+    #[test]
+    fn eval_mismatched_big_int_and_fixed_width_int() {
+        check(
+            "let a: Int = 1\na + 1u8",
+            Err(EvalError::TypeMismatch {
+                expected: "Int",
+                found: "u8",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_shift_discards_bits() {
+        check("255u8 << 4", Ok(Some(int_of_ty(240, IntTy::U8))));
+        check("1i8 << 7", Ok(Some(int_of_ty(-128, IntTy::I8))));
+        check("-16i8 >> 2", Ok(Some(int_of_ty(-4, IntTy::I8))));
+    }
+
+    #[test]
+    fn eval_bitwise_not() {
+        check("~0", Ok(Some(int(-1))));
+        check("~0u64 >> 60", Ok(Some(int_of_ty(15, IntTy::U64))));
+        check("~5u8", Ok(Some(int_of_ty(250, IntTy::U8))));
+    }
+
+    #[test]
+    fn eval_comparisons() {
+        check("1 < 2", Ok(Some(Value::Bool(true))));
+        check("2 <= 2", Ok(Some(Value::Bool(true))));
+        check("1 > 2", Ok(Some(Value::Bool(false))));
+        check("1 >= 2", Ok(Some(Value::Bool(false))));
+    }
+
+    #[test]
+    fn eval_equality() {
+        check("1 + 1 == 2", Ok(Some(Value::Bool(true))));
+        check("1 != 1", Ok(Some(Value::Bool(false))));
+        check("true == false", Ok(Some(Value::Bool(false))));
+        check("fn f() {}\nf == f", Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn eval_equality_of_different_types() {
+        check(
+            "1 == true",
+            Err(EvalError::TypeMismatch {
+                expected: "Int",
+                found: "Bool",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_ordering_of_booleans() {
+        check(
+            "true < false",
+            Err(EvalError::TypeMismatch {
+                expected: "{integer}",
+                found: "Bool",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_logical_operators() {
+        check("true && false", Ok(Some(Value::Bool(false))));
+        check("false || true", Ok(Some(Value::Bool(true))));
+        check("1 < 2 && 2 < 3 || false", Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn eval_not() {
+        check("!true", Ok(Some(Value::Bool(false))));
+        check("!(1 == 2)", Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn eval_not_of_number() {
+        check(
+            "!1",
+            Err(EvalError::TypeMismatch {
+                expected: "Bool",
+                found: "Int",
+            }),
+        );
+    }
+
+    #[test]
+    fn and_short_circuits() {
+        check("false && 1 / 0 == 1", Ok(Some(Value::Bool(false))));
+    }
+
+    #[test]
+    fn or_short_circuits() {
+        check("true || undefined", Ok(Some(Value::Bool(true))));
+    }
+
+    #[test]
+    fn eval_logical_operator_with_non_boolean_rhs() {
+        check(
+            "true && 1",
+            Err(EvalError::TypeMismatch {
+                expected: "Bool",
+                found: "Int",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_if_expr_with_true_condition() {
+        check("if true { 1 } else { 2 }", Ok(Some(int(1))));
+    }
+
+    #[test]
+    fn eval_if_expr_with_false_condition() {
+        check("if false { 1 } else { 2 }", Ok(Some(int(2))));
+    }
+
+    #[test]
+    fn eval_else_if() {
+        check(
+            "let a = false\nlet b = true\nif a { 1 } else if b { 2 } else { 3 }",
+            Ok(Some(int(2))),
+        );
+    }
+
+    #[test]
+    fn eval_if_expr_without_else() {
+        check("if false { 1 }", Ok(Some(Value::Unit)));
+    }
+
+    #[test]
+    fn eval_if_expr_only_evaluates_taken_branch() {
+        check("if true { 1 } else { 1 / 0 }", Ok(Some(int(1))));
+    }
+
+    #[test]
+    fn eval_if_expr_with_non_boolean_condition() {
+        check(
+            "if 1 { 2 }",
+            Err(EvalError::TypeMismatch {
+                expected: "Bool",
+                found: "Int",
+            }),
+        );
+    }
+
+    #[test]
+    fn eval_recursive_fn_with_if_expr() {
+        check(
+            "fn f(x, done) { if done { x } else { f(x * 2, true) } }\nf(3, false)",
+            Ok(Some(int(6))),
+        );
+    }
+
+    #[test]
+    fn eval_unbounded_recursion() {
+        check("fn f() { f() }\nf()", Err(EvalError::StackOverflow));
+    }
+
+    #[test]
+    fn eval_recursive_fn_in_block() {
+        check(
+            "{ fn fact(n) { if n == 0 { 1 } else { n * fact(n - 1) } }\nfact(5) }",
+            Ok(Some(int(120))),
+        );
+    }
+
+    #[test]
+    fn eval_call_to_sibling_fn_in_block() {
+        check(
+            "{ fn g() { 1 }\nfn h() { g() + 1 }\nh() }",
+            Ok(Some(int(2))),
+        );
+    }
+
+    #[test]
+    fn failed_call_pops_its_frame() {
+        let parse = parser::parse("fn f(x) { x / 0 }\nf(1)");
+        let root = ast::Root::cast(parse.syntax()).unwrap();
+        let (db, stmts, _) = hir::lower(root);
+        let (types, _) = typeck::check(&db, &stmts);
+
+        let mut evaluator = Evaluator::default();
+        assert_eq!(
+            evaluator.eval_stmts(&db, &types, &stmts),
+            Err(division_by_zero(10, 15)),
+        );
+        assert!(evaluator.scopes.is_empty());
+        assert!(evaluator.call_stack.is_empty());
+    }
+
+    #[test]
+    fn eval_block_expr() {
+        check("{ let a = 2\n a * 3 }", Ok(Some(int(6))));
+    }
+
+    #[test]
+    fn eval_block_expr_without_tail() {
+        check("{ let a = 1 }", Ok(Some(Value::Unit)));
+    }
+
+    #[test]
+    fn eval_empty_block_expr() {
+        check("{}", Ok(Some(Value::Unit)));
+    }
+
+    #[test]
+    fn blocks_see_enclosing_variables() {
+        check("let a = 1\n{ let b = 2\n { a + b } }", Ok(Some(int(3))));
+    }
+
+    #[test]
+    fn block_variables_shadow_enclosing_ones() {
+        check(
+            "let a = 1\nlet b = { let a = 10\n a }\na + b",
+            Ok(Some(int(11))),
+        );
+    }
+
+    #[test]
+    fn failed_block_pops_its_scope() {
+        let parse = parser::parse("{ let a = 1\n a / 0 }");
+        let root = ast::Root::cast(parse.syntax()).unwrap();
+        let (db, stmts, _) = hir::lower(root);
+        let (types, _) = typeck::check(&db, &stmts);
+
+        let mut evaluator = Evaluator::default();
+        assert_eq!(
+            evaluator.eval_stmts(&db, &types, &stmts),
+            Err(division_by_zero(13, 18)),
+        );
+        assert!(evaluator.scopes.is_empty());
+    }
+}
@@ -0,0 +1,39 @@
+use crate::Int;
+use bigint::BigInt;
+use hir::FnIdx;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(Int),
+    /// A value of the `Int` type
+    BigInt(BigInt),
+    Bool(bool),
+    Function(FnIdx),
+    /// The result of a function whose body doesn’t end in an expression
+    Unit,
+}
+
+impl Value {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Int(int) => int.ty().name(),
+            Self::BigInt(_) => "Int",
+            Self::Bool(_) => "Bool",
+            Self::Function(_) => "fn(…) -> …",
+            Self::Unit => "()",
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(int) => write!(f, "{}", int),
+            Self::BigInt(n) => write!(f, "{}", n),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Function(_) => write!(f, "<function>"),
+            Self::Unit => write!(f, "()"),
+        }
+    }
+}
@@ -1,148 +1,1169 @@
-use crate::{BinaryOp, Expr, Stmt, UnaryOp};
+use crate::lowering_error::{LoweringError, LoweringErrorKind};
+use crate::scopes::{SavedScopes, Scopes};
+use crate::source_map::SourceMap;
+use crate::{
+    BinaryOp, Binding, BindingIdx, BindingKind, Expr, ExprIdx, FnIdx, Function, IntTy, Param, Stmt,
+    TypeRef, UnaryOp,
+};
+use diagnostics::Diagnostic;
 use la_arena::Arena;
-use syntax::SyntaxKind;
+use smol_str::SmolStr;
+use std::mem;
+use std::ops::Index;
+use syntax::{SyntaxKind, SyntaxToken};
 
 #[derive(Debug, PartialEq, Default)]
 pub struct Database {
     exprs: Arena<Expr>,
+    fns: Arena<Function>,
+    bindings: Arena<Binding>,
+    source_map: SourceMap,
+    /// The names in scope at the point being lowered. Once lowering finishes only the global scope
+    /// is left, which later inputs are lowered in the context of.
+    scopes: Scopes,
+    errors: Vec<LoweringError>,
 }
 
 impl Database {
-    pub(crate) fn lower_stmt(&mut self, ast: ast::Stmt) -> Option<Stmt> {
-        let result = match ast {
-            ast::Stmt::VariableDef(ast) => Stmt::VariableDef {
-                name: ast.name()?.text().into(),
-                value: self.lower_expr(ast.value()),
+    /// Lowers `ast` into this database, which may already contain expressions and global bindings
+    /// from previous calls. Every statement is lowered, even if it has syntax errors: the parts
+    /// that are missing become `Expr::Missing` or unnamed bindings. Problems found while lowering,
+    /// such as names that can’t be resolved, are reported in the returned diagnostics.
+    pub fn lower_root(&mut self, ast: ast::Root) -> (Vec<Stmt>, Vec<Diagnostic>) {
+        self.source_map = SourceMap::default();
+
+        let stmts = self.lower_stmts(ast.stmts());
+
+        let diagnostics = mem::take(&mut self.errors)
+            .into_iter()
+            .map(LoweringError::into_diagnostic)
+            .collect();
+
+        (stmts, diagnostics)
+    }
+
+    /// Saves the names defined so far, so that an input whose bindings shouldn’t be kept, such as
+    /// one with errors, can be undone with `restore_scopes`.
+    pub fn save_scopes(&self) -> SavedScopes {
+        SavedScopes(self.scopes.clone())
+    }
+
+    pub fn restore_scopes(&mut self, saved: SavedScopes) {
+        self.scopes = saved.0;
+    }
+
+    /// Every expression lowered so far, in the order they were lowered in.
+    pub fn exprs(&self) -> impl ExactSizeIterator<Item = (ExprIdx, &Expr)> {
+        self.exprs.iter()
+    }
+
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    fn lower_stmts(&mut self, stmts: impl Iterator<Item = ast::Stmt>) -> Vec<Stmt> {
+        let stmts: Vec<_> = stmts.collect();
+
+        // Functions can be called by statements that come before their definition (which also
+        // allows mutual recursion), so they are all defined up front.
+        let fn_bindings: Vec<_> = stmts
+            .iter()
+            .map(|stmt| match stmt {
+                ast::Stmt::FnDef(ast) => Some(self.define(ast.name(), BindingKind::Function)),
+                _ => None,
+            })
+            .collect();
+
+        stmts
+            .into_iter()
+            .zip(fn_bindings)
+            .map(|(stmt, fn_binding)| match (stmt, fn_binding) {
+                (ast::Stmt::FnDef(ast), Some(binding)) => self.lower_fn_def(ast, binding),
+                (stmt, _) => self.lower_stmt(stmt),
+            })
+            .collect()
+    }
+
+    pub(crate) fn lower_stmt(&mut self, ast: ast::Stmt) -> Stmt {
+        match ast {
+            ast::Stmt::Break(_) => Stmt::Break,
+            ast::Stmt::Continue(_) => Stmt::Continue,
+            ast::Stmt::FnDef(ast) => {
+                let binding = self.define(ast.name(), BindingKind::Function);
+                self.lower_fn_def(ast, binding)
+            }
+            ast::Stmt::VariableDef(ast) => {
+                let type_ref = ast.type_ref().map(|ast| self.lower_type_ref(ast));
+                let value = self.lower_expr(ast.value());
+
+                // The variable is defined after its value is lowered so that the value can refer
+                // to a variable with the same name that the new one shadows.
+                let kind = BindingKind::Variable {
+                    mutable: ast.is_mutable(),
+                };
+                let binding = self.define(ast.name(), kind);
+
+                Stmt::VariableDef {
+                    binding,
+                    type_ref,
+                    value,
+                }
+            }
+            ast::Stmt::WhileLoop(ast) => Stmt::While {
+                condition: self.lower_expr(ast.condition()),
+                body: self.lower_expr(ast.body()),
             },
             ast::Stmt::Expr(ast) => Stmt::Expr(self.lower_expr(Some(ast))),
+        }
+    }
+
+    fn lower_fn_def(&mut self, ast: ast::FnDef, binding: BindingIdx) -> Stmt {
+        let bindings = &self.bindings;
+        let enclosing_scopes = self
+            .scopes
+            .enter_fn(|binding| bindings[binding].kind == BindingKind::Function);
+
+        let params = ast
+            .param_list()
+            .into_iter()
+            .flat_map(|param_list| param_list.params())
+            .map(|param| Param {
+                binding: self.define(param.name(), BindingKind::Param),
+                type_ref: param.type_ref().map(|ast| self.lower_type_ref(ast)),
+            })
+            .collect();
+
+        let body = self.lower_expr(ast.body().map(ast::Expr::BlockExpr));
+
+        self.scopes.exit_fn(enclosing_scopes);
+
+        let func = self.fns.alloc(Function { params, body });
+
+        Stmt::FnDef { binding, func }
+    }
+
+    fn lower_type_ref(&mut self, ast: ast::TypeRef) -> TypeRef {
+        let name = match ast.name() {
+            Some(name) => name,
+            None => return TypeRef::Missing,
         };
 
-        Some(result)
+        match name.text() {
+            "Bool" => TypeRef::Bool,
+            "Int" => TypeRef::BigInt,
+            name_text => match IntTy::from_name(name_text) {
+                Some(ty) => TypeRef::Int(ty),
+                None => {
+                    self.errors.push(LoweringError {
+                        kind: LoweringErrorKind::UnknownType {
+                            name: SmolStr::from(name.text()),
+                        },
+                        range: name.text_range(),
+                        definition: None,
+                    });
+
+                    TypeRef::Missing
+                }
+            },
+        }
     }
 
-    pub(crate) fn lower_expr(&mut self, ast: Option<ast::Expr>) -> Expr {
-        if let Some(ast) = ast {
-            match ast {
-                ast::Expr::BinaryExpr(ast) => self.lower_binary(ast),
-                ast::Expr::Literal(ast) => Expr::Literal { n: ast.parse() },
-                ast::Expr::ParenExpr(ast) => self.lower_expr(ast.expr()),
-                ast::Expr::UnaryExpr(ast) => self.lower_unary(ast),
-                ast::Expr::VariableRef(ast) => self.lower_variable_ref(ast),
+    /// Lowers `ast` and allocates it in the database, recording where it came from in the source
+    /// map.
+    pub(crate) fn lower_expr(&mut self, ast: Option<ast::Expr>) -> ExprIdx {
+        let ast = match ast {
+            Some(ast) => ast,
+            None => return self.exprs.alloc(Expr::Missing),
+        };
+
+        let expr = match &ast {
+            ast::Expr::AssignExpr(ast) => self.lower_assign(ast),
+            ast::Expr::BinaryExpr(ast) => self.lower_binary(ast),
+            ast::Expr::BlockExpr(ast) => self.lower_block(ast),
+            ast::Expr::CallExpr(ast) => self.lower_call(ast),
+            ast::Expr::IfExpr(ast) => self.lower_if(ast),
+            ast::Expr::Literal(ast) => match ast.kind() {
+                ast::LiteralKind::Number { value, suffix } => Expr::Literal { n: value, suffix },
+                ast::LiteralKind::Bool(value) => Expr::Bool { value },
+            },
+            ast::Expr::ParenExpr(paren_expr) => {
+                let expr = self.lower_expr(paren_expr.expr());
+                self.source_map.insert_syntax(ast.syntax(), expr);
+
+                return expr;
             }
-        } else {
-            Expr::Missing
-        }
+            ast::Expr::UnaryExpr(ast) => self.lower_unary(ast),
+            ast::Expr::VariableRef(ast) => self.lower_variable_ref(ast),
+        };
+
+        let expr = self.exprs.alloc(expr);
+        self.source_map.insert_expr(expr, ast.syntax());
+
+        expr
     }
 
-    fn lower_binary(&mut self, ast: ast::BinaryExpr) -> Expr {
-        let op = match ast.op().unwrap().kind() {
-            SyntaxKind::Plus => BinaryOp::Add,
-            SyntaxKind::Minus => BinaryOp::Sub,
-            SyntaxKind::Star => BinaryOp::Mul,
-            SyntaxKind::Slash => BinaryOp::Div,
-            _ => unreachable!(),
+    fn lower_assign(&mut self, ast: &ast::AssignExpr) -> Expr {
+        let value = self.lower_expr(ast.value());
+
+        let op = match ast.op().map(|op| op.kind()) {
+            Some(SyntaxKind::Equals) => None,
+            Some(SyntaxKind::PlusEquals) => Some(BinaryOp::Add),
+            Some(SyntaxKind::MinusEquals) => Some(BinaryOp::Sub),
+            Some(SyntaxKind::StarEquals) => Some(BinaryOp::Mul),
+            Some(SyntaxKind::SlashEquals) => Some(BinaryOp::Div),
+            _ => return Expr::Missing,
+        };
+
+        // Validation reports assignments to anything other than a variable.
+        let name = match ast.target() {
+            Some(ast::Expr::VariableRef(target)) => match target.name() {
+                Some(name) => name,
+                None => return Expr::Missing,
+            },
+            _ => return Expr::Missing,
+        };
+
+        let binding = match self.resolve(&name) {
+            Some(binding) => binding,
+            None => return Expr::Missing,
+        };
+
+        let kind = match self.bindings[binding].kind {
+            BindingKind::Variable { mutable: true } => None,
+            BindingKind::Variable { mutable: false } => {
+                Some(LoweringErrorKind::AssignToImmutableVariable)
+            }
+            BindingKind::Param => Some(LoweringErrorKind::AssignToParam),
+            BindingKind::Function => Some(LoweringErrorKind::AssignToFunction),
         };
 
+        if let Some(kind) = kind {
+            self.errors.push(LoweringError {
+                kind,
+                range: name.text_range(),
+                definition: self.source_map.binding_range(binding),
+            });
+        }
+
+        Expr::Assign { binding, op, value }
+    }
+
+    fn lower_binary(&mut self, ast: &ast::BinaryExpr) -> Expr {
+        // The operands are lowered even if the operator is unknown, so that they can still be
+        // analyzed.
         let lhs = self.lower_expr(ast.lhs());
         let rhs = self.lower_expr(ast.rhs());
 
-        Expr::Binary {
-            op,
-            lhs: self.exprs.alloc(lhs),
-            rhs: self.exprs.alloc(rhs),
-        }
+        let op = match ast.op().map(|op| op.kind()) {
+            Some(SyntaxKind::Plus) => BinaryOp::Add,
+            Some(SyntaxKind::Minus) => BinaryOp::Sub,
+            Some(SyntaxKind::Star) => BinaryOp::Mul,
+            Some(SyntaxKind::Slash) => BinaryOp::Div,
+            Some(SyntaxKind::Percent) => BinaryOp::Rem,
+            Some(SyntaxKind::StarStar) => BinaryOp::Pow,
+            Some(SyntaxKind::Amp) => BinaryOp::BitAnd,
+            Some(SyntaxKind::Pipe) => BinaryOp::BitOr,
+            Some(SyntaxKind::Caret) => BinaryOp::BitXor,
+            Some(SyntaxKind::LessLess) => BinaryOp::Shl,
+            Some(SyntaxKind::GreaterGreater) => BinaryOp::Shr,
+            Some(SyntaxKind::EqualsEquals) => BinaryOp::Eq,
+            Some(SyntaxKind::BangEquals) => BinaryOp::NotEq,
+            Some(SyntaxKind::Less) => BinaryOp::Less,
+            Some(SyntaxKind::LessEquals) => BinaryOp::LessEq,
+            Some(SyntaxKind::Greater) => BinaryOp::Greater,
+            Some(SyntaxKind::GreaterEquals) => BinaryOp::GreaterEq,
+            Some(SyntaxKind::AmpAmp) => BinaryOp::And,
+            Some(SyntaxKind::PipePipe) => BinaryOp::Or,
+            _ => return Expr::Missing,
+        };
+
+        Expr::Binary { op, lhs, rhs }
     }
 
-    fn lower_unary(&mut self, ast: ast::UnaryExpr) -> Expr {
-        let op = match ast.op().unwrap().kind() {
-            SyntaxKind::Minus => UnaryOp::Neg,
-            _ => unreachable!(),
+    fn lower_block(&mut self, ast: &ast::BlockExpr) -> Expr {
+        self.scopes.push();
+        let mut stmts = self.lower_stmts(ast.stmts());
+        self.scopes.pop();
+
+        let tail = match stmts.pop() {
+            Some(Stmt::Expr(expr)) => Some(expr),
+            Some(stmt) => {
+                stmts.push(stmt);
+                None
+            }
+            None => None,
         };
 
+        Expr::Block { stmts, tail }
+    }
+
+    fn lower_call(&mut self, ast: &ast::CallExpr) -> Expr {
+        let callee = self.lower_expr(ast.callee().map(ast::Expr::VariableRef));
+
+        let args = ast
+            .arg_list()
+            .into_iter()
+            .flat_map(|arg_list| arg_list.args())
+            .map(|arg| self.lower_expr(Some(arg)))
+            .collect();
+
+        Expr::Call { callee, args }
+    }
+
+    fn lower_if(&mut self, ast: &ast::IfExpr) -> Expr {
+        let condition = self.lower_expr(ast.condition());
+        let then_branch = self.lower_expr(ast.then_branch());
+        let else_branch = ast
+            .else_branch()
+            .map(|else_branch| self.lower_expr(Some(else_branch)));
+
+        Expr::If {
+            condition,
+            then_branch,
+            else_branch,
+        }
+    }
+
+    fn lower_unary(&mut self, ast: &ast::UnaryExpr) -> Expr {
         let expr = self.lower_expr(ast.expr());
 
-        Expr::Unary {
-            op,
-            expr: self.exprs.alloc(expr),
+        let op = match ast.op().map(|op| op.kind()) {
+            Some(SyntaxKind::Minus) => UnaryOp::Neg,
+            Some(SyntaxKind::Bang) => UnaryOp::Not,
+            Some(SyntaxKind::Tilde) => UnaryOp::BitNot,
+            _ => return Expr::Missing,
+        };
+
+        Expr::Unary { op, expr }
+    }
+
+    fn lower_variable_ref(&mut self, ast: &ast::VariableRef) -> Expr {
+        let binding = match ast.name() {
+            Some(name) => self.resolve(&name),
+            None => None,
+        };
+
+        match binding {
+            Some(binding) => Expr::VariableRef { binding },
+            None => Expr::Missing,
         }
     }
 
-    fn lower_variable_ref(&mut self, ast: ast::VariableRef) -> Expr {
-        Expr::VariableRef {
-            var: ast.name().unwrap().text().into(),
+    /// Defines a new binding for `name` in the current scope. If the definition has no name the
+    /// binding is still allocated so that the rest of the definition can be lowered, but it is
+    /// left unnamed and can’t be referred to.
+    fn define(&mut self, name: Option<SyntaxToken>, kind: BindingKind) -> BindingIdx {
+        let name = match name {
+            Some(name) => name,
+            None => {
+                return self.bindings.alloc(Binding {
+                    name: SmolStr::default(),
+                    kind,
+                })
+            }
+        };
+
+        let binding = self.bindings.alloc(Binding {
+            name: name.text().into(),
+            kind,
+        });
+
+        self.source_map.insert_binding(binding, name.text_range());
+        self.scopes.define(name.text().into(), binding);
+
+        binding
+    }
+
+    /// Looks up the binding `name` refers to, reporting an error if there isn’t one.
+    fn resolve(&mut self, name: &SyntaxToken) -> Option<BindingIdx> {
+        let binding = self.scopes.lookup(name.text());
+
+        if binding.is_none() {
+            self.errors.push(LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable {
+                    name: SmolStr::from(name.text()),
+                },
+                range: name.text_range(),
+                definition: None,
+            });
         }
+
+        binding
+    }
+}
+
+impl Index<ExprIdx> for Database {
+    type Output = Expr;
+
+    fn index(&self, idx: ExprIdx) -> &Self::Output {
+        &self.exprs[idx]
+    }
+}
+
+impl Index<FnIdx> for Database {
+    type Output = Function;
+
+    fn index(&self, idx: FnIdx) -> &Self::Output {
+        &self.fns[idx]
+    }
+}
+
+impl Index<BindingIdx> for Database {
+    type Output = Binding;
+
+    fn index(&self, idx: BindingIdx) -> &Self::Output {
+        &self.bindings[idx]
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bigint::BigInt;
+    use la_arena::{Idx, RawIdx};
 
     fn parse(input: &str) -> ast::Root {
         ast::Root::cast(parser::parse(input).syntax()).unwrap()
     }
 
-    fn check_stmt(input: &str, expected_hir: Stmt) {
+    /// A database with a mutable variable `a`, an immutable variable `b` and a function `f`
+    /// defined globally, for tests to refer to.
+    fn globals() -> Database {
+        let mut database = Database::default();
+
+        for (name, kind) in [
+            ("a", BindingKind::Variable { mutable: true }),
+            ("b", BindingKind::Variable { mutable: false }),
+            ("f", BindingKind::Function),
+        ] {
+            let binding = database.bindings.alloc(Binding {
+                name: name.into(),
+                kind,
+            });
+            database.scopes.define(name.into(), binding);
+        }
+
+        database
+    }
+
+    fn global(name: &str) -> BindingIdx {
+        globals().scopes.lookup(name).unwrap()
+    }
+
+    fn check_stmt(input: &str, expected_hir: Stmt, expected_exprs: Arena<Expr>) {
         let root = parse(input);
         let ast = root.stmts().next().unwrap();
-        let hir = Database::default().lower_stmt(ast).unwrap();
+        let mut database = globals();
+        let hir = database.lower_stmt(ast);
 
         assert_eq!(hir, expected_hir);
+        assert_eq!(database.exprs, expected_exprs);
     }
 
-    fn check_expr(input: &str, expected_hir: Expr, expected_database: Database) {
+    /// Checks that `input` lowers to `expected_hir`, which is allocated after the expressions in
+    /// `expected_exprs` since subexpressions are lowered first.
+    fn check_expr(input: &str, expected_hir: Expr, mut expected_exprs: Arena<Expr>) {
         let root = parse(input);
         let first_stmt = root.stmts().next().unwrap();
         let ast = match first_stmt {
             ast::Stmt::Expr(ast) => ast,
             _ => unreachable!(),
         };
-        let mut database = Database::default();
+        let mut database = globals();
         let hir = database.lower_expr(Some(ast));
 
-        assert_eq!(hir, expected_hir);
-        assert_eq!(database, expected_database);
+        assert_eq!(hir, expected_exprs.alloc(expected_hir));
+        assert_eq!(database.exprs, expected_exprs);
+        assert_eq!(database.errors, Vec::new());
+    }
+
+    fn check_errors(input: &str, expected_errors: Vec<LoweringError>) {
+        let (_, diagnostics) = globals().lower_root(parse(input));
+
+        let expected_diagnostics: Vec<_> = expected_errors
+            .into_iter()
+            .map(LoweringError::into_diagnostic)
+            .collect();
+
+        assert_eq!(diagnostics, expected_diagnostics);
+    }
+
+    fn range(start: u32, end: u32) -> text_size::TextRange {
+        text_size::TextRange::new(start.into(), end.into())
     }
 
     #[test]
     fn lower_variable_def() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::VariableRef {
+            binding: global("b"),
+        });
+
         check_stmt(
-            "let foo = bar",
+            "let foo = b",
             Stmt::VariableDef {
-                name: "foo".into(),
-                value: Expr::VariableRef { var: "bar".into() },
+                binding: Idx::from_raw(RawIdx::from(3)),
+                type_ref: None,
+                value,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_variable_def_with_type() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Bool { value: true });
+
+        check_stmt(
+            "let a: Bool = true",
+            Stmt::VariableDef {
+                binding: Idx::from_raw(RawIdx::from(3)),
+                type_ref: Some(TypeRef::Bool),
+                value,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_variable_def_with_int_type() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+
+        check_stmt(
+            "let a: i32 = 1",
+            Stmt::VariableDef {
+                binding: Idx::from_raw(RawIdx::from(3)),
+                type_ref: Some(TypeRef::Int(IntTy::I32)),
+                value,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_variable_def_with_big_int_type() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Literal {
+            n: "100000000000000000000000000000".parse().unwrap(),
+            suffix: None,
+        });
+
+        check_stmt(
+            "let a: Int = 100000000000000000000000000000",
+            Stmt::VariableDef {
+                binding: Idx::from_raw(RawIdx::from(3)),
+                type_ref: Some(TypeRef::BigInt),
+                value,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_variable_def_with_unknown_type() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+
+        check_stmt(
+            "let a: Num = 1",
+            Stmt::VariableDef {
+                binding: Idx::from_raw(RawIdx::from(3)),
+                type_ref: Some(TypeRef::Missing),
+                value,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_mutable_variable_def() {
+        let mut database = Database::default();
+        let (stmts, _) = database.lower_root(parse("let mut a = 1"));
+
+        let mut bindings = Arena::new();
+        let binding = bindings.alloc(Binding {
+            name: "a".into(),
+            kind: BindingKind::Variable { mutable: true },
+        });
+
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+
+        assert_eq!(
+            stmts,
+            vec![Stmt::VariableDef {
+                binding,
+                type_ref: None,
+                value,
+            }]
+        );
+        assert_eq!(database.exprs, exprs);
+        assert_eq!(database.bindings, bindings);
+        assert_eq!(
+            database.source_map.binding_range(binding),
+            Some(range(8, 9)),
+        );
+        assert_eq!(database.scopes.lookup("a"), Some(binding));
+    }
+
+    #[test]
+    fn variable_def_value_refers_to_shadowed_variable() {
+        let mut database = Database::default();
+        let (stmts, _) = database.lower_root(parse("let a = 1\nlet a = a"));
+
+        let first = Idx::from_raw(RawIdx::from(0));
+        let second = Idx::from_raw(RawIdx::from(1));
+
+        let value = match stmts[1] {
+            Stmt::VariableDef { binding, value, .. } => {
+                assert_eq!(binding, second);
+                value
+            }
+            _ => unreachable!(),
+        };
+
+        assert_eq!(database[value], Expr::VariableRef { binding: first });
+        assert_eq!(database.scopes.lookup("a"), Some(second));
+    }
+
+    #[test]
+    fn block_variables_are_not_visible_after_block() {
+        check_errors(
+            "{ let c = 1 }\nc",
+            vec![LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable { name: "c".into() },
+                range: range(14, 15),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn fn_bodies_cannot_see_enclosing_locals() {
+        check_errors(
+            "{ let c = 1\nfn g() { c } }",
+            vec![LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable { name: "c".into() },
+                range: range(21, 22),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn fns_in_blocks_can_call_themselves_and_their_siblings() {
+        check_errors(
+            "{ fn g(n) { if n == 0 { 0 } else { g(n - 1) } }\nfn h() { g(3) }\nh() }",
+            Vec::new(),
+        );
+    }
+
+    #[test]
+    fn nested_fns_can_call_enclosing_fns() {
+        check_errors("fn f() { fn g() { f() }\ng() }", Vec::new());
+    }
+
+    #[test]
+    fn loop_body_variables_are_not_visible_after_loop() {
+        check_errors(
+            "while true { let c = 1\nbreak }\nc",
+            vec![LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable { name: "c".into() },
+                range: range(31, 32),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn fn_locals_are_not_visible_outside_fn() {
+        check_errors(
+            "fn g(x) { let y = x }\ny",
+            vec![LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable { name: "y".into() },
+                range: range(22, 23),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn params_are_not_visible_to_other_fns() {
+        check_errors(
+            "fn g() { x }\nfn h(x) { g() }",
+            vec![LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable { name: "x".into() },
+                range: range(9, 10),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn fns_can_be_called_before_their_definition() {
+        check_errors("fn g() { h() }\nfn h() { g() }\ng()", Vec::new());
+    }
+
+    #[test]
+    fn undefined_variable() {
+        check_errors(
+            "1 + foo",
+            vec![LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable { name: "foo".into() },
+                range: range(4, 7),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn undefined_variable_diagnostic() {
+        let (_, diagnostics) = Database::default().lower_root(parse("foo"));
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error("E0006", "undefined variable ‘foo’", range(0, 3))
+                    .with_label("not found in this scope")
+            ],
+        );
+    }
+
+    #[test]
+    fn unknown_type_diagnostic() {
+        let (_, diagnostics) = Database::default().lower_root(parse("let a: Num = 1"));
+
+        assert_eq!(
+            diagnostics,
+            vec![
+                Diagnostic::error("E0009", "unknown type ‘Num’", range(7, 10))
+                    .with_label("not a known type")
+                    .with_note("the built-in types are ‘Bool’, ‘Int’ and the fixed-width integer types ‘i8’, ‘i16’, ‘i32’, ‘i64’, ‘u8’, ‘u16’, ‘u32’ and ‘u64’")
+            ],
+        );
+    }
+
+    #[test]
+    fn assign_to_immutable_variable_diagnostic() {
+        let (_, diagnostics) = Database::default().lower_root(parse("let a = 1\na = 2"));
+
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                "E0004",
+                "cannot assign to an immutable variable",
+                range(10, 11),
+            )
+            .with_label("cannot assign twice to an immutable variable")
+            .with_secondary_label(range(4, 5), "first defined here")
+            .with_suggestion("make this variable mutable", range(4, 4), "mut ")],
+        );
+    }
+
+    #[test]
+    fn assign_to_immutable_variable_from_earlier_input() {
+        let mut database = Database::default();
+        database.lower_root(parse("let a = 1"));
+        let (_, diagnostics) = database.lower_root(parse("a = 2"));
+
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic::error(
+                "E0004",
+                "cannot assign to an immutable variable",
+                range(0, 1),
+            )
+            .with_label("cannot assign twice to an immutable variable")],
+        );
+    }
+
+    #[test]
+    fn assign_to_shadowing_variable() {
+        check_errors(
+            "{ let a = 2\na = 3 }\na = 4",
+            vec![LoweringError {
+                kind: LoweringErrorKind::AssignToImmutableVariable,
+                range: range(12, 13),
+                definition: Some(range(6, 7)),
+            }],
+        );
+    }
+
+    #[test]
+    fn assign_to_param() {
+        check_errors(
+            "fn g(x) { x = 1 }",
+            vec![LoweringError {
+                kind: LoweringErrorKind::AssignToParam,
+                range: range(10, 11),
+                definition: Some(range(5, 6)),
+            }],
+        );
+    }
+
+    #[test]
+    fn assign_to_function() {
+        check_errors(
+            "f += 1",
+            vec![LoweringError {
+                kind: LoweringErrorKind::AssignToFunction,
+                range: range(0, 1),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn assign_to_undefined_variable() {
+        check_errors(
+            "c = 1",
+            vec![LoweringError {
+                kind: LoweringErrorKind::UndefinedVariable { name: "c".into() },
+                range: range(0, 1),
+                definition: None,
+            }],
+        );
+    }
+
+    #[test]
+    fn lower_assign() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Literal {
+            n: BigInt::from(5),
+            suffix: None,
+        });
+
+        check_expr(
+            "a = 5",
+            Expr::Assign {
+                binding: global("a"),
+                op: None,
+                value,
             },
+            exprs,
         );
     }
 
+    #[test]
+    fn lower_compound_assign() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::VariableRef {
+            binding: global("b"),
+        });
+
+        check_expr(
+            "a *= b",
+            Expr::Assign {
+                binding: global("a"),
+                op: Some(BinaryOp::Mul),
+                value,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_assign_to_non_variable() {
+        let mut exprs = Arena::new();
+        exprs.alloc(Expr::Literal {
+            n: BigInt::from(2),
+            suffix: None,
+        });
+
+        check_expr("1 = 2", Expr::Missing, exprs);
+    }
+
     #[test]
     fn lower_variable_def_without_name() {
-        let root = parse("let = 10");
+        let mut database = globals();
+        let (stmts, diagnostics) = database.lower_root(parse("let = 10"));
+
+        let binding = Idx::from_raw(RawIdx::from(3));
+
+        // The parser recovers from the missing name by skipping `=` and `10`.
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Missing);
+
+        assert_eq!(
+            stmts,
+            vec![Stmt::VariableDef {
+                binding,
+                type_ref: None,
+                value,
+            }]
+        );
+        assert_eq!(database.exprs, exprs);
+        assert_eq!(
+            database[binding],
+            Binding {
+                name: SmolStr::default(),
+                kind: BindingKind::Variable { mutable: false },
+            },
+        );
+        assert_eq!(database.source_map.binding_range(binding), None);
+        assert_eq!(diagnostics, Vec::new());
+    }
+
+    #[test]
+    fn lower_fn_def() {
+        let root = parse("fn add(x, y) { x + y }");
+        let ast = root.stmts().next().unwrap();
+        let mut database = Database::default();
+        let hir = database.lower_stmt(ast);
+
+        let mut bindings = Arena::new();
+        let add = bindings.alloc(Binding {
+            name: "add".into(),
+            kind: BindingKind::Function,
+        });
+        let x = bindings.alloc(Binding {
+            name: "x".into(),
+            kind: BindingKind::Param,
+        });
+        let y = bindings.alloc(Binding {
+            name: "y".into(),
+            kind: BindingKind::Param,
+        });
+
+        let mut exprs = Arena::new();
+        let x_ref = exprs.alloc(Expr::VariableRef { binding: x });
+        let y_ref = exprs.alloc(Expr::VariableRef { binding: y });
+        let sum = exprs.alloc(Expr::Binary {
+            op: BinaryOp::Add,
+            lhs: x_ref,
+            rhs: y_ref,
+        });
+        let body = exprs.alloc(Expr::Block {
+            stmts: Vec::new(),
+            tail: Some(sum),
+        });
+
+        let mut fns = Arena::new();
+        let func = fns.alloc(Function {
+            params: vec![
+                Param {
+                    binding: x,
+                    type_ref: None,
+                },
+                Param {
+                    binding: y,
+                    type_ref: None,
+                },
+            ],
+            body,
+        });
+
+        assert_eq!(hir, Stmt::FnDef { binding: add, func });
+        assert_eq!(database.exprs, exprs);
+        assert_eq!(database.fns, fns);
+        assert_eq!(database.bindings, bindings);
+
+        // Parameters are only in scope inside the function’s body.
+        assert_eq!(database.scopes.lookup("add"), Some(add));
+        assert_eq!(database.scopes.lookup("x"), None);
+    }
+
+    #[test]
+    fn lower_params_with_types() {
+        let mut database = Database::default();
+        let (stmts, _) = database.lower_root(parse("fn f(x: u8, y) {}"));
+
+        let func = match stmts[..] {
+            [Stmt::FnDef { func, .. }] => func,
+            _ => unreachable!(),
+        };
+        let type_refs: Vec<_> = database[func]
+            .params
+            .iter()
+            .map(|param| param.type_ref)
+            .collect();
+
+        assert_eq!(type_refs, vec![Some(TypeRef::Int(IntTy::U8)), None]);
+    }
+
+    #[test]
+    fn lower_fn_def_without_name() {
+        let mut database = Database::default();
+        let (stmts, diagnostics) = database.lower_root(parse("fn\nlet a = 1"));
+
+        let mut bindings = Arena::new();
+        let binding = bindings.alloc(Binding {
+            name: SmolStr::default(),
+            kind: BindingKind::Function,
+        });
+        let a = bindings.alloc(Binding {
+            name: "a".into(),
+            kind: BindingKind::Variable { mutable: false },
+        });
+
+        let mut exprs = Arena::new();
+        let body = exprs.alloc(Expr::Missing);
+        let value = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+
+        let mut fns = Arena::new();
+        let func = fns.alloc(Function {
+            params: Vec::new(),
+            body,
+        });
+
+        assert_eq!(
+            stmts,
+            vec![
+                Stmt::FnDef { binding, func },
+                Stmt::VariableDef {
+                    binding: a,
+                    type_ref: None,
+                    value,
+                },
+            ],
+        );
+        assert_eq!(database.bindings, bindings);
+        assert_eq!(database.exprs, exprs);
+        assert_eq!(database.fns, fns);
+        assert_eq!(diagnostics, Vec::new());
+    }
+
+    #[test]
+    fn lower_every_prefix_of_input() {
+        let input = "fn add(x, y) { x + y }\nlet mut a = -add(1, (2))\nwhile a < 10 { a += 1 }";
+
+        for end in 0..=input.len() {
+            let mut database = Database::default();
+            let (stmts, _) = database.lower_root(parse(&input[..end]));
+
+            assert_eq!(stmts.len(), parse(&input[..end]).stmts().count());
+        }
+    }
+
+    #[test]
+    fn unnamed_definitions_are_not_in_scope() {
+        let mut database = Database::default();
+        database.lower_root(parse("let = 1\nfn () {}"));
+
+        assert_eq!(database.scopes.lookup(""), None);
+    }
+
+    #[test]
+    fn lower_fn_def_without_body() {
+        let root = parse("fn f()");
         let ast = root.stmts().next().unwrap();
-        assert!(Database::default().lower_stmt(ast).is_none());
+        let mut database = Database::default();
+        let hir = database.lower_stmt(ast);
+
+        let mut exprs = Arena::new();
+        let body = exprs.alloc(Expr::Missing);
+
+        let mut fns = Arena::new();
+        let func = fns.alloc(Function {
+            params: Vec::new(),
+            body,
+        });
+
+        assert_eq!(
+            hir,
+            Stmt::FnDef {
+                binding: Idx::from_raw(RawIdx::from(0)),
+                func,
+            },
+        );
+        assert_eq!(database.exprs, exprs);
+        assert_eq!(database.fns, fns);
+    }
+
+    #[test]
+    fn lower_while_loop() {
+        let mut exprs = Arena::new();
+        let condition = exprs.alloc(Expr::Bool { value: true });
+        let a = exprs.alloc(Expr::VariableRef {
+            binding: global("a"),
+        });
+        let body = exprs.alloc(Expr::Block {
+            stmts: vec![Stmt::Expr(a), Stmt::Break, Stmt::Continue],
+            tail: None,
+        });
+
+        check_stmt(
+            "while true { a\nbreak\ncontinue }",
+            Stmt::While { condition, body },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_while_loop_without_body() {
+        let mut exprs = Arena::new();
+        let condition = exprs.alloc(Expr::VariableRef {
+            binding: global("a"),
+        });
+        let body = exprs.alloc(Expr::Missing);
+
+        check_stmt("while a", Stmt::While { condition, body }, exprs);
+    }
+
+    #[test]
+    fn lower_while_loop_without_condition() {
+        let mut exprs = Arena::new();
+        let condition = exprs.alloc(Expr::Missing);
+        let body = exprs.alloc(Expr::Block {
+            stmts: vec![Stmt::Break],
+            tail: None,
+        });
+
+        check_stmt("while { break }", Stmt::While { condition, body }, exprs);
     }
 
     #[test]
     fn lower_variable_def_without_value() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Missing);
+
         check_stmt(
-            "let a =",
+            "let c =",
             Stmt::VariableDef {
-                name: "a".into(),
-                value: Expr::Missing,
+                binding: Idx::from_raw(RawIdx::from(3)),
+                type_ref: None,
+                value,
             },
+            exprs,
         );
     }
 
     #[test]
     fn lower_expr_stmt() {
-        check_stmt("123", Stmt::Expr(Expr::Literal { n: Some(123) }));
+        let mut exprs = Arena::new();
+        let expr = exprs.alloc(Expr::Literal {
+            n: BigInt::from(123),
+            suffix: None,
+        });
+
+        check_stmt("123", Stmt::Expr(expr), exprs);
     }
 
     #[test]
     fn lower_binary_expr() {
         let mut exprs = Arena::new();
-        let lhs = exprs.alloc(Expr::Literal { n: Some(1) });
-        let rhs = exprs.alloc(Expr::Literal { n: Some(2) });
+        let lhs = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+        let rhs = exprs.alloc(Expr::Literal {
+            n: BigInt::from(2),
+            suffix: None,
+        });
 
         check_expr(
             "1 + 2",
@@ -151,14 +1172,75 @@ mod tests {
                 rhs,
                 op: BinaryOp::Add,
             },
-            Database { exprs },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_bitwise_expr() {
+        let mut exprs = Arena::new();
+        let lhs = exprs.alloc(Expr::Literal {
+            n: BigInt::from(255),
+            suffix: None,
+        });
+        let a = exprs.alloc(Expr::VariableRef {
+            binding: global("a"),
+        });
+        let one = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+        let rhs = exprs.alloc(Expr::Binary {
+            lhs: a,
+            rhs: one,
+            op: BinaryOp::Shl,
+        });
+
+        check_expr(
+            "255 & a << 1",
+            Expr::Binary {
+                lhs,
+                rhs,
+                op: BinaryOp::BitAnd,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_logical_expr() {
+        let mut exprs = Arena::new();
+        let a = exprs.alloc(Expr::VariableRef {
+            binding: global("a"),
+        });
+        let b = exprs.alloc(Expr::VariableRef {
+            binding: global("b"),
+        });
+        let lhs = exprs.alloc(Expr::Binary {
+            lhs: a,
+            rhs: b,
+            op: BinaryOp::Less,
+        });
+        let rhs = exprs.alloc(Expr::Bool { value: false });
+
+        check_expr(
+            "a < b || false",
+            Expr::Binary {
+                lhs,
+                rhs,
+                op: BinaryOp::Or,
+            },
+            exprs,
         );
     }
 
     #[test]
     fn lower_binary_expr_without_rhs() {
         let mut exprs = Arena::new();
-        let lhs = exprs.alloc(Expr::Literal { n: Some(10) });
+        let lhs = exprs.alloc(Expr::Literal {
+            n: BigInt::from(10),
+            suffix: None,
+        });
         let rhs = exprs.alloc(Expr::Missing);
 
         check_expr(
@@ -168,28 +1250,255 @@ mod tests {
                 rhs,
                 op: BinaryOp::Sub,
             },
-            Database { exprs },
+            exprs,
         );
     }
 
     #[test]
     fn lower_literal() {
-        check_expr("999", Expr::Literal { n: Some(999) }, Database::default());
+        check_expr(
+            "999",
+            Expr::Literal {
+                n: BigInt::from(999),
+                suffix: None,
+            },
+            Arena::new(),
+        );
+    }
+
+    #[test]
+    fn lower_literal_with_suffix() {
+        check_expr(
+            "255u8",
+            Expr::Literal {
+                n: BigInt::from(255),
+                suffix: Some(IntTy::U8),
+            },
+            Arena::new(),
+        );
+    }
+
+    #[test]
+    fn lower_boolean_literal() {
+        check_expr("false", Expr::Bool { value: false }, Arena::new());
+    }
+
+    #[test]
+    fn lower_if_expr() {
+        let mut exprs = Arena::new();
+        let condition = exprs.alloc(Expr::VariableRef {
+            binding: global("a"),
+        });
+        let one = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+        let then_branch = exprs.alloc(Expr::Block {
+            stmts: Vec::new(),
+            tail: Some(one),
+        });
+        let two = exprs.alloc(Expr::Literal {
+            n: BigInt::from(2),
+            suffix: None,
+        });
+        let else_branch = exprs.alloc(Expr::Block {
+            stmts: Vec::new(),
+            tail: Some(two),
+        });
+
+        check_expr(
+            "if a { 1 } else { 2 }",
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch: Some(else_branch),
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_if_expr_without_else() {
+        let mut exprs = Arena::new();
+        let condition = exprs.alloc(Expr::Bool { value: true });
+        let then_branch = exprs.alloc(Expr::Block {
+            stmts: Vec::new(),
+            tail: None,
+        });
+
+        check_expr(
+            "if true {}",
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch: None,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_if_expr_without_then_branch() {
+        let mut exprs = Arena::new();
+        let condition = exprs.alloc(Expr::VariableRef {
+            binding: global("a"),
+        });
+        let then_branch = exprs.alloc(Expr::Missing);
+
+        check_expr(
+            "if a",
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch: None,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_if_expr_without_condition() {
+        let mut exprs = Arena::new();
+        let condition = exprs.alloc(Expr::Missing);
+        let one = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+        let then_branch = exprs.alloc(Expr::Block {
+            stmts: Vec::new(),
+            tail: Some(one),
+        });
+
+        check_expr(
+            "if { 1 }",
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch: None,
+            },
+            exprs,
+        );
     }
 
     #[test]
     fn lower_paren_expr() {
         check_expr(
-            "((((((abc))))))",
-            Expr::VariableRef { var: "abc".into() },
-            Database::default(),
+            "((((((b))))))",
+            Expr::VariableRef {
+                binding: global("b"),
+            },
+            Arena::new(),
+        );
+    }
+
+    #[test]
+    fn lower_block_expr() {
+        // The block’s `a` shadows the global one.
+        let binding = Idx::from_raw(RawIdx::from(3));
+
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+        let a = exprs.alloc(Expr::VariableRef { binding });
+
+        check_expr(
+            "{ let a = 1\n a }",
+            Expr::Block {
+                stmts: vec![Stmt::VariableDef {
+                    binding,
+                    type_ref: None,
+                    value,
+                }],
+                tail: Some(a),
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_block_expr_without_tail() {
+        let mut exprs = Arena::new();
+        let value = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+
+        check_expr(
+            "{ let a = 1 }",
+            Expr::Block {
+                stmts: vec![Stmt::VariableDef {
+                    binding: Idx::from_raw(RawIdx::from(3)),
+                    type_ref: None,
+                    value,
+                }],
+                tail: None,
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_empty_block_expr() {
+        check_expr(
+            "{}",
+            Expr::Block {
+                stmts: Vec::new(),
+                tail: None,
+            },
+            Arena::new(),
+        );
+    }
+
+    #[test]
+    fn lower_call() {
+        let mut exprs = Arena::new();
+        let callee = exprs.alloc(Expr::VariableRef {
+            binding: global("f"),
+        });
+        let one = exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+        let a = exprs.alloc(Expr::VariableRef {
+            binding: global("a"),
+        });
+
+        check_expr(
+            "f(1, a)",
+            Expr::Call {
+                callee,
+                args: vec![one, a],
+            },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_call_without_args() {
+        let mut exprs = Arena::new();
+        let callee = exprs.alloc(Expr::VariableRef {
+            binding: global("f"),
+        });
+
+        check_expr(
+            "f()",
+            Expr::Call {
+                callee,
+                args: Vec::new(),
+            },
+            exprs,
         );
     }
 
     #[test]
     fn lower_unary_expr() {
         let mut exprs = Arena::new();
-        let ten = exprs.alloc(Expr::Literal { n: Some(10) });
+        let ten = exprs.alloc(Expr::Literal {
+            n: BigInt::from(10),
+            suffix: None,
+        });
 
         check_expr(
             "-10",
@@ -197,7 +1506,22 @@ mod tests {
                 expr: ten,
                 op: UnaryOp::Neg,
             },
-            Database { exprs },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_not_expr() {
+        let mut exprs = Arena::new();
+        let expr = exprs.alloc(Expr::Bool { value: true });
+
+        check_expr(
+            "!true",
+            Expr::Unary {
+                expr,
+                op: UnaryOp::Not,
+            },
+            exprs,
         );
     }
 
@@ -212,16 +1536,85 @@ mod tests {
                 expr,
                 op: UnaryOp::Neg,
             },
-            Database { exprs },
+            exprs,
+        );
+    }
+
+    #[test]
+    fn lower_into_existing_database() {
+        let mut database = Database::default();
+        database.lower_root(parse("1 + 2"));
+        let (stmts, _) = database.lower_root(parse("-3"));
+
+        let mut exprs = Arena::new();
+        exprs.alloc(Expr::Literal {
+            n: BigInt::from(1),
+            suffix: None,
+        });
+        exprs.alloc(Expr::Literal {
+            n: BigInt::from(2),
+            suffix: None,
+        });
+        exprs.alloc(Expr::Binary {
+            op: BinaryOp::Add,
+            lhs: Idx::from_raw(RawIdx::from(0)),
+            rhs: Idx::from_raw(RawIdx::from(1)),
+        });
+        let three = exprs.alloc(Expr::Literal {
+            n: BigInt::from(3),
+            suffix: None,
+        });
+        let neg = exprs.alloc(Expr::Unary {
+            expr: three,
+            op: UnaryOp::Neg,
+        });
+
+        assert_eq!(stmts, vec![Stmt::Expr(neg)]);
+        assert_eq!(database.exprs, exprs);
+    }
+
+    #[test]
+    fn globals_persist_across_inputs() {
+        let mut database = Database::default();
+        database.lower_root(parse("let c = 1"));
+        let (stmts, diagnostics) = database.lower_root(parse("c"));
+
+        let c = match stmts[..] {
+            [Stmt::Expr(c)] => c,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            database[c],
+            Expr::VariableRef {
+                binding: Idx::from_raw(RawIdx::from(0)),
+            },
         );
+        assert_eq!(diagnostics, Vec::new());
     }
 
     #[test]
     fn lower_variable_ref() {
         check_expr(
-            "foo",
-            Expr::VariableRef { var: "foo".into() },
-            Database::default(),
+            "f",
+            Expr::VariableRef {
+                binding: global("f"),
+            },
+            Arena::new(),
         );
     }
+
+    #[test]
+    fn lower_undefined_variable_ref() {
+        let root = parse("foo");
+        let ast = match root.stmts().next().unwrap() {
+            ast::Stmt::Expr(ast) => ast,
+            _ => unreachable!(),
+        };
+
+        let mut database = Database::default();
+        let expr = database.lower_expr(Some(ast));
+
+        assert_eq!(database[expr], Expr::Missing);
+    }
 }
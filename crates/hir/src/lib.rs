@@ -1,35 +1,123 @@
 mod database;
+mod lowering_error;
+mod scopes;
+mod source_map;
+
+pub use ast::IntTy;
 pub use database::Database;
+pub use scopes::SavedScopes;
+pub use source_map::SourceMap;
 
+use bigint::BigInt;
+use diagnostics::Diagnostic;
 use la_arena::Idx;
 use smol_str::SmolStr;
 
-type ExprIdx = Idx<Expr>;
+pub type BindingIdx = Idx<Binding>;
+pub type ExprIdx = Idx<Expr>;
+pub type FnIdx = Idx<Function>;
 
 #[derive(Debug, PartialEq)]
 pub enum Stmt {
-    VariableDef { name: SmolStr, value: Expr },
-    Expr(Expr),
+    Break,
+    Continue,
+    FnDef {
+        binding: BindingIdx,
+        func: FnIdx,
+    },
+    VariableDef {
+        binding: BindingIdx,
+        type_ref: Option<TypeRef>,
+        value: ExprIdx,
+    },
+    While {
+        condition: ExprIdx,
+        body: ExprIdx,
+    },
+    Expr(ExprIdx),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Function {
+    pub params: Vec<Param>,
+    pub body: ExprIdx,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Param {
+    pub binding: BindingIdx,
+    pub type_ref: Option<TypeRef>,
+}
+
+/// A type written out in the source, such as the `u8` in `let x: u8 = 5`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeRef {
+    /// A type that is missing or whose name isn’t known
+    Missing,
+    Int(IntTy),
+    /// `Int`, whose values can be arbitrarily large
+    BigInt,
+    Bool,
+}
+
+/// Something a name can refer to. Every variable, parameter and function definition gets its own
+/// binding, so two bindings with the same name are still distinct.
+#[derive(Debug, PartialEq)]
+pub struct Binding {
+    /// is empty if the definition is missing its name
+    pub name: SmolStr,
+    pub kind: BindingKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BindingKind {
+    Variable { mutable: bool },
+    Param,
+    Function,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum Expr {
     Missing,
+    Assign {
+        binding: BindingIdx,
+        /// the operator of a compound assignment such as `+=`, or `None` for plain `=`
+        op: Option<BinaryOp>,
+        value: ExprIdx,
+    },
     Binary {
         op: BinaryOp,
         lhs: ExprIdx,
         rhs: ExprIdx,
     },
+    Block {
+        stmts: Vec<Stmt>,
+        /// the expression the block ends with, which gives the block its value
+        tail: Option<ExprIdx>,
+    },
+    Bool {
+        value: bool,
+    },
+    Call {
+        callee: ExprIdx,
+        args: Vec<ExprIdx>,
+    },
+    If {
+        condition: ExprIdx,
+        then_branch: ExprIdx,
+        else_branch: Option<ExprIdx>,
+    },
     Literal {
-        /// is `None` if the number is too big to fit in a u64
-        n: Option<u64>,
+        n: BigInt,
+        /// the type given by the literal’s suffix, such as the `u8` in `5u8`
+        suffix: Option<IntTy>,
     },
     Unary {
         op: UnaryOp,
         expr: ExprIdx,
     },
     VariableRef {
-        var: SmolStr,
+        binding: BindingIdx,
     },
 }
 
@@ -39,16 +127,35 @@ pub enum BinaryOp {
     Sub,
     Mul,
     Div,
+    Rem,
+    Pow,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    Eq,
+    NotEq,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+    /// only evaluates its right-hand side if the left-hand side is true
+    And,
+    /// only evaluates its right-hand side if the left-hand side is false
+    Or,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum UnaryOp {
     Neg,
+    Not,
+    BitNot,
 }
 
-pub fn lower(ast: ast::Root) -> (Database, Vec<Stmt>) {
+pub fn lower(ast: ast::Root) -> (Database, Vec<Stmt>, Vec<Diagnostic>) {
     let mut db = Database::default();
-    let stmts = ast.stmts().filter_map(|stmt| db.lower_stmt(stmt)).collect();
+    let (stmts, diagnostics) = db.lower_root(ast);
 
-    (db, stmts)
+    (db, stmts, diagnostics)
 }
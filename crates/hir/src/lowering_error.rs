@@ -0,0 +1,96 @@
+use diagnostics::Diagnostic;
+use smol_str::SmolStr;
+use std::fmt;
+use text_size::TextRange;
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct LoweringError {
+    pub(crate) kind: LoweringErrorKind,
+    pub(crate) range: TextRange,
+    /// Where the binding an error refers to was defined, if it refers to one that was defined in
+    /// the same input
+    pub(crate) definition: Option<TextRange>,
+}
+
+impl LoweringError {
+    pub(crate) fn into_diagnostic(self) -> Diagnostic {
+        let diagnostic = Diagnostic::error(self.kind.code(), self.kind.to_string(), self.range);
+
+        match self.kind {
+            LoweringErrorKind::UndefinedVariable { .. } => {
+                diagnostic.with_label("not found in this scope")
+            }
+            LoweringErrorKind::UnknownType { .. } => diagnostic
+                .with_label("not a known type")
+                .with_note("the built-in types are ‘Bool’, ‘Int’ and the fixed-width integer types ‘i8’, ‘i16’, ‘i32’, ‘i64’, ‘u8’, ‘u16’, ‘u32’ and ‘u64’"),
+            LoweringErrorKind::AssignToImmutableVariable => {
+                let diagnostic =
+                    diagnostic.with_label("cannot assign twice to an immutable variable");
+
+                match self.definition {
+                    Some(definition) => diagnostic
+                        .with_secondary_label(definition, "first defined here")
+                        .with_suggestion(
+                            "make this variable mutable",
+                            TextRange::empty(definition.start()),
+                            "mut ",
+                        ),
+                    None => diagnostic,
+                }
+            }
+            LoweringErrorKind::AssignToParam => {
+                let diagnostic = diagnostic.with_label("cannot assign to this parameter");
+
+                match self.definition {
+                    Some(definition) => {
+                        diagnostic.with_secondary_label(definition, "parameter defined here")
+                    }
+                    None => diagnostic,
+                }
+            }
+            LoweringErrorKind::AssignToFunction => {
+                let diagnostic = diagnostic.with_label("cannot assign to this function");
+
+                match self.definition {
+                    Some(definition) => {
+                        diagnostic.with_secondary_label(definition, "function defined here")
+                    }
+                    None => diagnostic,
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum LoweringErrorKind {
+    UndefinedVariable { name: SmolStr },
+    UnknownType { name: SmolStr },
+    AssignToImmutableVariable,
+    AssignToParam,
+    AssignToFunction,
+}
+
+impl LoweringErrorKind {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::AssignToImmutableVariable | Self::AssignToParam | Self::AssignToFunction => {
+                "E0004"
+            }
+            Self::UndefinedVariable { .. } => "E0006",
+            Self::UnknownType { .. } => "E0009",
+        }
+    }
+}
+
+impl fmt::Display for LoweringErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UndefinedVariable { name } => write!(f, "undefined variable ‘{}’", name),
+            Self::UnknownType { name } => write!(f, "unknown type ‘{}’", name),
+            Self::AssignToImmutableVariable => write!(f, "cannot assign to an immutable variable"),
+            Self::AssignToParam => write!(f, "cannot assign to a function parameter"),
+            Self::AssignToFunction => write!(f, "cannot assign to a function"),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::BindingIdx;
+use smol_str::SmolStr;
+use std::collections::HashMap;
+
+type Scope = HashMap<SmolStr, BindingIdx>;
+
+/// The names that are visible at the point being lowered, mapped to the bindings they refer to.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Scopes {
+    /// The global scope comes first and the innermost scope last.
+    scopes: Vec<Scope>,
+}
+
+/// The names defined by the inputs lowered into a `Database` so far, saved with
+/// `Database::save_scopes`.
+#[derive(Debug)]
+pub struct SavedScopes(pub(crate) Scopes);
+
+impl Default for Scopes {
+    fn default() -> Self {
+        Self {
+            scopes: vec![Scope::new()],
+        }
+    }
+}
+
+impl Scopes {
+    pub(crate) fn push(&mut self) {
+        self.scopes.push(Scope::new());
+    }
+
+    pub(crate) fn pop(&mut self) {
+        assert!(self.scopes.len() > 1, "cannot pop the global scope");
+        self.scopes.pop();
+    }
+
+    /// Hides the local variables in every scope other than the global one and starts a new scope
+    /// for a function’s parameters, since function bodies can’t see the local variables around
+    /// their definition. The functions defined around it, for which `is_fn` returns true, stay
+    /// visible so that it can call itself and its siblings. The hidden scopes are returned so
+    /// that they can be restored with `exit_fn`.
+    pub(crate) fn enter_fn(&mut self, is_fn: impl Fn(BindingIdx) -> bool) -> Vec<Scope> {
+        let enclosing_scopes = self.scopes.split_off(1);
+
+        self.scopes.extend(enclosing_scopes.iter().map(|scope| {
+            scope
+                .iter()
+                .filter(|(_, binding)| is_fn(**binding))
+                .map(|(name, binding)| (name.clone(), *binding))
+                .collect()
+        }));
+        self.push();
+
+        enclosing_scopes
+    }
+
+    pub(crate) fn exit_fn(&mut self, enclosing_scopes: Vec<Scope>) {
+        self.scopes.truncate(1);
+        self.scopes.extend(enclosing_scopes);
+    }
+
+    /// Defines `name` in the innermost scope, shadowing any previous binding with the same name.
+    pub(crate) fn define(&mut self, name: SmolStr, binding: BindingIdx) {
+        self.scopes.last_mut().unwrap().insert(name, binding);
+    }
+
+    pub(crate) fn lookup(&self, name: &str) -> Option<BindingIdx> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use la_arena::{Idx, RawIdx};
+
+    fn binding(n: u32) -> BindingIdx {
+        Idx::from_raw(RawIdx::from(n))
+    }
+
+    #[test]
+    fn inner_scope_shadows_outer_scope() {
+        let mut scopes = Scopes::default();
+        scopes.define("a".into(), binding(0));
+        scopes.push();
+        scopes.define("a".into(), binding(1));
+
+        assert_eq!(scopes.lookup("a"), Some(binding(1)));
+
+        scopes.pop();
+        assert_eq!(scopes.lookup("a"), Some(binding(0)));
+    }
+
+    #[test]
+    fn functions_only_see_global_scope_and_enclosing_functions() {
+        let mut scopes = Scopes::default();
+        scopes.define("global".into(), binding(0));
+        scopes.push();
+        scopes.define("local".into(), binding(1));
+        scopes.define("sibling".into(), binding(2));
+
+        let enclosing_scopes = scopes.enter_fn(|idx| idx == binding(2));
+        assert_eq!(scopes.lookup("global"), Some(binding(0)));
+        assert_eq!(scopes.lookup("local"), None);
+        assert_eq!(scopes.lookup("sibling"), Some(binding(2)));
+
+        scopes.exit_fn(enclosing_scopes);
+        assert_eq!(scopes.lookup("local"), Some(binding(1)));
+    }
+}
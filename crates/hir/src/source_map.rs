@@ -0,0 +1,177 @@
+use crate::{BindingIdx, ExprIdx};
+use la_arena::ArenaMap;
+use std::collections::HashMap;
+use syntax::{SyntaxNode, SyntaxNodePtr};
+use text_size::TextRange;
+
+/// Links the HIR in a `Database` back to the syntax it was lowered from, and vice versa.
+///
+/// Only the most recently lowered input is covered, since the ranges of earlier inputs refer to
+/// different text.
+#[derive(Debug, PartialEq, Default)]
+pub struct SourceMap {
+    expr_syntax: ArenaMap<ExprIdx, SyntaxNodePtr>,
+    expr_ranges: ArenaMap<ExprIdx, TextRange>,
+    syntax_expr: HashMap<SyntaxNodePtr, ExprIdx>,
+    binding_ranges: ArenaMap<BindingIdx, TextRange>,
+}
+
+impl SourceMap {
+    /// Returns the node `expr` was lowered from. Expressions that were missing from the syntax
+    /// tree don’t have one.
+    pub fn expr_syntax(&self, expr: ExprIdx) -> Option<SyntaxNodePtr> {
+        self.expr_syntax.get(expr).copied()
+    }
+
+    /// Returns the range of the node `expr` was lowered from, without the whitespace and comments
+    /// around it. This is the range errors about `expr` should point at.
+    pub fn expr_range(&self, expr: ExprIdx) -> Option<TextRange> {
+        self.expr_ranges.get(expr).copied()
+    }
+
+    /// Returns the expression `node` was lowered into. Parenthesized expressions are mapped to the
+    /// same expression as their contents.
+    pub fn syntax_expr(&self, node: &SyntaxNode) -> Option<ExprIdx> {
+        self.syntax_expr.get(&SyntaxNodePtr::new(node)).copied()
+    }
+
+    /// Returns the range of the name `binding` was defined with.
+    pub fn binding_range(&self, binding: BindingIdx) -> Option<TextRange> {
+        self.binding_ranges.get(binding).copied()
+    }
+
+    pub(crate) fn insert_expr(&mut self, expr: ExprIdx, node: &SyntaxNode) {
+        let ptr = SyntaxNodePtr::new(node);
+
+        self.expr_syntax.insert(expr, ptr);
+        self.expr_ranges.insert(expr, ast::trimmed_range(node));
+        self.syntax_expr.insert(ptr, expr);
+    }
+
+    /// Maps `node` to `expr` without mapping `expr` back to `node`, for nodes such as parentheses
+    /// that don’t get an expression of their own.
+    pub(crate) fn insert_syntax(&mut self, node: &SyntaxNode, expr: ExprIdx) {
+        self.syntax_expr.insert(SyntaxNodePtr::new(node), expr);
+    }
+
+    pub(crate) fn insert_binding(&mut self, binding: BindingIdx, range: TextRange) {
+        self.binding_ranges.insert(binding, range);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Database, Expr, Stmt, UnaryOp};
+    use syntax::{SyntaxKind, SyntaxNode};
+    use text_size::TextRange;
+
+    fn lower(database: &mut Database, input: &str) -> (SyntaxNode, Vec<Stmt>) {
+        let syntax = parser::parse(input).syntax();
+        let (stmts, _) = database.lower_root(ast::Root::cast(syntax.clone()).unwrap());
+
+        (syntax, stmts)
+    }
+
+    fn find(root: &SyntaxNode, kind: SyntaxKind) -> SyntaxNode {
+        root.descendants().find(|node| node.kind() == kind).unwrap()
+    }
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(start.into(), end.into())
+    }
+
+    #[test]
+    fn map_expr_to_trimmed_range() {
+        let mut database = Database::default();
+        let (_, stmts) = lower(&mut database, "1 + 2 # sum\n");
+        let sum = match stmts[..] {
+            [Stmt::Expr(sum)] => sum,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(database.source_map().expr_range(sum), Some(range(0, 5)));
+    }
+
+    #[test]
+    fn map_expr_to_syntax_and_back() {
+        let mut database = Database::default();
+        let (root, _) = lower(&mut database, "let a = -1");
+        let neg = find(&root, SyntaxKind::PrefixExpr);
+        let one = find(&root, SyntaxKind::Literal);
+        let source_map = database.source_map();
+
+        let expr = source_map.syntax_expr(&neg).unwrap();
+        assert_eq!(
+            database[expr],
+            Expr::Unary {
+                op: UnaryOp::Neg,
+                expr: source_map.syntax_expr(&one).unwrap(),
+            },
+        );
+
+        let ptr = source_map.expr_syntax(expr).unwrap();
+        assert_eq!(ptr.to_node(&root), neg);
+    }
+
+    #[test]
+    fn map_paren_expr_to_inner_expr() {
+        let mut database = Database::default();
+        let (root, stmts) = lower(&mut database, "(2)");
+        let two = match stmts[..] {
+            [Stmt::Expr(two)] => two,
+            _ => unreachable!(),
+        };
+        let source_map = database.source_map();
+
+        assert_eq!(
+            source_map.syntax_expr(&find(&root, SyntaxKind::ParenExpr)),
+            Some(two),
+        );
+        assert_eq!(
+            source_map.expr_syntax(two).unwrap().kind(),
+            SyntaxKind::Literal,
+        );
+    }
+
+    #[test]
+    fn missing_expr_has_no_syntax() {
+        let mut database = Database::default();
+        let (_, stmts) = lower(&mut database, "let a =");
+        let value = match stmts[..] {
+            [Stmt::VariableDef { value, .. }] => value,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(database[value], Expr::Missing);
+        assert_eq!(database.source_map().expr_syntax(value), None);
+        assert_eq!(database.source_map().expr_range(value), None);
+    }
+
+    #[test]
+    fn map_binding_to_range() {
+        let mut database = Database::default();
+        let (_, stmts) = lower(&mut database, "let abc = 1");
+        let binding = match stmts[..] {
+            [Stmt::VariableDef { binding, .. }] => binding,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(
+            database.source_map().binding_range(binding),
+            Some(range(4, 7)),
+        );
+    }
+
+    #[test]
+    fn only_latest_input_is_mapped() {
+        let mut database = Database::default();
+        let (_, first) = lower(&mut database, "10");
+        lower(&mut database, "20");
+        let ten = match first[..] {
+            [Stmt::Expr(ten)] => ten,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(database.source_map().expr_range(ten), None);
+    }
+}
@@ -12,10 +12,34 @@ pub enum TokenKind {
     #[token("let")]
     LetKw,
 
+    #[token("mut")]
+    MutKw,
+
+    #[token("if")]
+    IfKw,
+
+    #[token("else")]
+    ElseKw,
+
+    #[token("true")]
+    TrueKw,
+
+    #[token("false")]
+    FalseKw,
+
+    #[token("while")]
+    WhileKw,
+
+    #[token("break")]
+    BreakKw,
+
+    #[token("continue")]
+    ContinueKw,
+
     #[regex("[A-Za-z][A-Za-z0-9]*")]
     Ident,
 
-    #[regex("[0-9]+")]
+    #[regex("[0-9]+([iu](8|16|32|64))?")]
     Number,
 
     #[token("+")]
@@ -30,9 +54,78 @@ pub enum TokenKind {
     #[token("/")]
     Slash,
 
+    #[token("%")]
+    Percent,
+
+    #[token("**")]
+    StarStar,
+
+    #[token("&")]
+    Amp,
+
+    #[token("|")]
+    Pipe,
+
+    #[token("^")]
+    Caret,
+
+    #[token("<<")]
+    LessLess,
+
+    #[token(">>")]
+    GreaterGreater,
+
+    #[token("~")]
+    Tilde,
+
     #[token("=")]
     Equals,
 
+    #[token("+=")]
+    PlusEquals,
+
+    #[token("-=")]
+    MinusEquals,
+
+    #[token("*=")]
+    StarEquals,
+
+    #[token("/=")]
+    SlashEquals,
+
+    #[token("==")]
+    EqualsEquals,
+
+    #[token("!=")]
+    BangEquals,
+
+    #[token("<")]
+    Less,
+
+    #[token("<=")]
+    LessEquals,
+
+    #[token(">")]
+    Greater,
+
+    #[token(">=")]
+    GreaterEquals,
+
+    #[token("&&")]
+    AmpAmp,
+
+    #[token("||")]
+    PipePipe,
+
+    #[token("!")]
+    Bang,
+
+    #[token(",")]
+    Comma,
+
+    #[token(":")]
+    Colon,
+
     #[token("(")]
     LParen,
 
@@ -64,13 +157,44 @@ impl fmt::Display for TokenKind {
             Self::Whitespace => "whitespace",
             Self::FnKw => "‘fn’",
             Self::LetKw => "‘let’",
+            Self::MutKw => "‘mut’",
+            Self::IfKw => "‘if’",
+            Self::ElseKw => "‘else’",
+            Self::TrueKw => "‘true’",
+            Self::FalseKw => "‘false’",
+            Self::WhileKw => "‘while’",
+            Self::BreakKw => "‘break’",
+            Self::ContinueKw => "‘continue’",
             Self::Ident => "identifier",
             Self::Number => "number",
             Self::Plus => "‘+’",
             Self::Minus => "‘-’",
             Self::Star => "‘*’",
             Self::Slash => "‘/’",
+            Self::Percent => "‘%’",
+            Self::StarStar => "‘**’",
+            Self::Amp => "‘&’",
+            Self::Pipe => "‘|’",
+            Self::Caret => "‘^’",
+            Self::LessLess => "‘<<’",
+            Self::GreaterGreater => "‘>>’",
+            Self::Tilde => "‘~’",
             Self::Equals => "‘=’",
+            Self::PlusEquals => "‘+=’",
+            Self::MinusEquals => "‘-=’",
+            Self::StarEquals => "‘*=’",
+            Self::SlashEquals => "‘/=’",
+            Self::EqualsEquals => "‘==’",
+            Self::BangEquals => "‘!=’",
+            Self::Less => "‘<’",
+            Self::LessEquals => "‘<=’",
+            Self::Greater => "‘>’",
+            Self::GreaterEquals => "‘>=’",
+            Self::AmpAmp => "‘&&’",
+            Self::PipePipe => "‘||’",
+            Self::Bang => "‘!’",
+            Self::Comma => "‘,’",
+            Self::Colon => "‘:’",
             Self::LParen => "‘(’",
             Self::RParen => "‘)’",
             Self::LBrace => "‘{’",
@@ -109,6 +233,51 @@ mod tests {
         check("let", TokenKind::LetKw);
     }
 
+    #[test]
+    fn lex_mut_keyword() {
+        check("mut", TokenKind::MutKw);
+    }
+
+    #[test]
+    fn lex_if_keyword() {
+        check("if", TokenKind::IfKw);
+    }
+
+    #[test]
+    fn lex_else_keyword() {
+        check("else", TokenKind::ElseKw);
+    }
+
+    #[test]
+    fn lex_true_keyword() {
+        check("true", TokenKind::TrueKw);
+    }
+
+    #[test]
+    fn lex_false_keyword() {
+        check("false", TokenKind::FalseKw);
+    }
+
+    #[test]
+    fn lex_while_keyword() {
+        check("while", TokenKind::WhileKw);
+    }
+
+    #[test]
+    fn lex_break_keyword() {
+        check("break", TokenKind::BreakKw);
+    }
+
+    #[test]
+    fn lex_continue_keyword() {
+        check("continue", TokenKind::ContinueKw);
+    }
+
+    #[test]
+    fn lex_identifier_starting_with_keyword() {
+        check("iffy", TokenKind::Ident);
+    }
+
     #[test]
     fn lex_alphabetic_identifier() {
         check("abcd", TokenKind::Ident);
@@ -134,6 +303,12 @@ mod tests {
         check("123456", TokenKind::Number);
     }
 
+    #[test]
+    fn lex_number_with_suffix() {
+        check("255u8", TokenKind::Number);
+        check("1i64", TokenKind::Number);
+    }
+
     #[test]
     fn lex_plus() {
         check("+", TokenKind::Plus);
@@ -154,11 +329,126 @@ mod tests {
         check("/", TokenKind::Slash);
     }
 
+    #[test]
+    fn lex_percent() {
+        check("%", TokenKind::Percent);
+    }
+
+    #[test]
+    fn lex_double_star() {
+        check("**", TokenKind::StarStar);
+    }
+
+    #[test]
+    fn lex_ampersand() {
+        check("&", TokenKind::Amp);
+    }
+
+    #[test]
+    fn lex_pipe() {
+        check("|", TokenKind::Pipe);
+    }
+
+    #[test]
+    fn lex_caret() {
+        check("^", TokenKind::Caret);
+    }
+
+    #[test]
+    fn lex_double_less_than() {
+        check("<<", TokenKind::LessLess);
+    }
+
+    #[test]
+    fn lex_double_greater_than() {
+        check(">>", TokenKind::GreaterGreater);
+    }
+
+    #[test]
+    fn lex_tilde() {
+        check("~", TokenKind::Tilde);
+    }
+
     #[test]
     fn lex_equals() {
         check("=", TokenKind::Equals);
     }
 
+    #[test]
+    fn lex_plus_equals() {
+        check("+=", TokenKind::PlusEquals);
+    }
+
+    #[test]
+    fn lex_minus_equals() {
+        check("-=", TokenKind::MinusEquals);
+    }
+
+    #[test]
+    fn lex_star_equals() {
+        check("*=", TokenKind::StarEquals);
+    }
+
+    #[test]
+    fn lex_slash_equals() {
+        check("/=", TokenKind::SlashEquals);
+    }
+
+    #[test]
+    fn lex_double_equals() {
+        check("==", TokenKind::EqualsEquals);
+    }
+
+    #[test]
+    fn lex_bang_equals() {
+        check("!=", TokenKind::BangEquals);
+    }
+
+    #[test]
+    fn lex_less_than() {
+        check("<", TokenKind::Less);
+    }
+
+    #[test]
+    fn lex_less_than_or_equal() {
+        check("<=", TokenKind::LessEquals);
+    }
+
+    #[test]
+    fn lex_greater_than() {
+        check(">", TokenKind::Greater);
+    }
+
+    #[test]
+    fn lex_greater_than_or_equal() {
+        check(">=", TokenKind::GreaterEquals);
+    }
+
+    #[test]
+    fn lex_double_ampersand() {
+        check("&&", TokenKind::AmpAmp);
+    }
+
+    #[test]
+    fn lex_double_pipe() {
+        check("||", TokenKind::PipePipe);
+    }
+
+    #[test]
+    fn lex_bang() {
+        check("!", TokenKind::Bang);
+    }
+
+    #[test]
+    fn lex_comma() {
+        check(",", TokenKind::Comma);
+    }
+
+    #[test]
+    fn lex_colon() {
+        check(":", TokenKind::Colon);
+    }
+
     #[test]
     fn lex_left_parenthesis() {
         check("(", TokenKind::LParen);
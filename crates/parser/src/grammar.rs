@@ -1,5 +1,6 @@
 mod expr;
 mod stmt;
+mod type_ref;
 
 use crate::parser::marker::CompletedMarker;
 use crate::parser::Parser;
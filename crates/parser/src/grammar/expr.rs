@@ -16,6 +16,46 @@ fn expr_binding_power(p: &mut Parser, minimum_binding_power: u8) -> Option<Compl
             BinaryOp::Mul
         } else if p.at(TokenKind::Slash) {
             BinaryOp::Div
+        } else if p.at(TokenKind::Percent) {
+            BinaryOp::Rem
+        } else if p.at(TokenKind::StarStar) {
+            BinaryOp::Pow
+        } else if p.at(TokenKind::Amp) {
+            BinaryOp::BitAnd
+        } else if p.at(TokenKind::Pipe) {
+            BinaryOp::BitOr
+        } else if p.at(TokenKind::Caret) {
+            BinaryOp::BitXor
+        } else if p.at(TokenKind::LessLess) {
+            BinaryOp::Shl
+        } else if p.at(TokenKind::GreaterGreater) {
+            BinaryOp::Shr
+        } else if p.at(TokenKind::EqualsEquals) {
+            BinaryOp::Eq
+        } else if p.at(TokenKind::BangEquals) {
+            BinaryOp::NotEq
+        } else if p.at(TokenKind::Less) {
+            BinaryOp::Less
+        } else if p.at(TokenKind::LessEquals) {
+            BinaryOp::LessEq
+        } else if p.at(TokenKind::Greater) {
+            BinaryOp::Greater
+        } else if p.at(TokenKind::GreaterEquals) {
+            BinaryOp::GreaterEq
+        } else if p.at(TokenKind::AmpAmp) {
+            BinaryOp::And
+        } else if p.at(TokenKind::PipePipe) {
+            BinaryOp::Or
+        } else if p.at(TokenKind::Equals) {
+            BinaryOp::Assign
+        } else if p.at(TokenKind::PlusEquals) {
+            BinaryOp::AddAssign
+        } else if p.at(TokenKind::MinusEquals) {
+            BinaryOp::SubAssign
+        } else if p.at(TokenKind::StarEquals) {
+            BinaryOp::MulAssign
+        } else if p.at(TokenKind::SlashEquals) {
+            BinaryOp::DivAssign
         } else {
             // We’re not at an operator; we don’t know what to do next, so we return and let the
             // caller decide.
@@ -33,7 +73,14 @@ fn expr_binding_power(p: &mut Parser, minimum_binding_power: u8) -> Option<Compl
 
         let m = lhs.precede(p);
         let parsed_rhs = expr_binding_power(p, right_binding_power).is_some();
-        lhs = m.complete(p, SyntaxKind::InfixExpr);
+        lhs = m.complete(
+            p,
+            if op.is_assignment() {
+                SyntaxKind::AssignExpr
+            } else {
+                SyntaxKind::InfixExpr
+            },
+        );
 
         if !parsed_rhs {
             break;
@@ -44,14 +91,24 @@ fn expr_binding_power(p: &mut Parser, minimum_binding_power: u8) -> Option<Compl
 }
 
 fn lhs(p: &mut Parser) -> Option<CompletedMarker> {
-    let cm = if p.at(TokenKind::Number) {
+    let cm = if p.at(TokenKind::Number) || p.at(TokenKind::TrueKw) || p.at(TokenKind::FalseKw) {
         literal(p)
     } else if p.at(TokenKind::Ident) {
-        variable_ref(p)
-    } else if p.at(TokenKind::Minus) {
+        let var_ref = variable_ref(p);
+
+        if p.at(TokenKind::LParen) {
+            call_expr(p, var_ref)
+        } else {
+            var_ref
+        }
+    } else if p.at(TokenKind::Minus) || p.at(TokenKind::Bang) || p.at(TokenKind::Tilde) {
         prefix_expr(p)
     } else if p.at(TokenKind::LParen) {
         paren_expr(p)
+    } else if p.at(TokenKind::LBrace) {
+        block_expr(p)
+    } else if p.at(TokenKind::IfKw) {
+        if_expr(p)
     } else {
         p.error();
         return None;
@@ -65,31 +122,82 @@ enum BinaryOp {
     Sub,
     Mul,
     Div,
+    Rem,
+    Pow,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    Eq,
+    NotEq,
+    Less,
+    LessEq,
+    Greater,
+    GreaterEq,
+    And,
+    Or,
+    Assign,
+    AddAssign,
+    SubAssign,
+    MulAssign,
+    DivAssign,
 }
 
 impl BinaryOp {
     fn binding_power(&self) -> (u8, u8) {
         match self {
-            Self::Add | Self::Sub => (1, 2),
-            Self::Mul | Self::Div => (3, 4),
+            // Assignment is right-associative and binds the loosest, so `a = b = 1 + 2` is
+            // `a = (b = (1 + 2))`.
+            Self::Assign
+            | Self::AddAssign
+            | Self::SubAssign
+            | Self::MulAssign
+            | Self::DivAssign => (2, 1),
+            Self::Or => (3, 4),
+            Self::And => (5, 6),
+            Self::Eq
+            | Self::NotEq
+            | Self::Less
+            | Self::LessEq
+            | Self::Greater
+            | Self::GreaterEq => (7, 8),
+            Self::BitOr => (9, 10),
+            Self::BitXor => (11, 12),
+            Self::BitAnd => (13, 14),
+            Self::Shl | Self::Shr => (15, 16),
+            Self::Add | Self::Sub => (17, 18),
+            Self::Mul | Self::Div | Self::Rem => (19, 20),
+            // Exponentiation is right-associative, and binds tighter than prefix operators so
+            // that `-2 ** 2` is `-(2 ** 2)`.
+            Self::Pow => (22, 21),
         }
     }
+
+    fn is_assignment(&self) -> bool {
+        matches!(
+            self,
+            Self::Assign | Self::AddAssign | Self::SubAssign | Self::MulAssign | Self::DivAssign,
+        )
+    }
 }
 
 enum UnaryOp {
     Neg,
+    Not,
+    BitNot,
 }
 
 impl UnaryOp {
     fn binding_power(&self) -> ((), u8) {
         match self {
-            Self::Neg => ((), 5),
+            Self::Neg | Self::Not | Self::BitNot => ((), 21),
         }
     }
 }
 
 fn literal(p: &mut Parser) -> CompletedMarker {
-    assert!(p.at(TokenKind::Number));
+    assert!(p.at(TokenKind::Number) || p.at(TokenKind::TrueKw) || p.at(TokenKind::FalseKw));
 
     let m = p.start();
     p.bump();
@@ -104,12 +212,94 @@ fn variable_ref(p: &mut Parser) -> CompletedMarker {
     m.complete(p, SyntaxKind::VariableRef)
 }
 
+fn call_expr(p: &mut Parser, callee: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(TokenKind::LParen));
+
+    let m = callee.precede(p);
+    arg_list(p);
+
+    m.complete(p, SyntaxKind::CallExpr)
+}
+
+fn arg_list(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LParen));
+
+    let m = p.start();
+    p.bump();
+
+    while !p.at(TokenKind::RParen) && !p.at_end() {
+        if expr(p).is_none() {
+            break;
+        }
+
+        // The missing ‘)’ is reported below, so there’s no need to also ask for a comma at the end
+        // of the input.
+        if !p.at(TokenKind::RParen) && !p.at_end() {
+            p.expect(TokenKind::Comma);
+        }
+    }
+
+    p.expect(TokenKind::RParen);
+
+    m.complete(p, SyntaxKind::ArgList)
+}
+
+pub(super) fn block_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LBrace));
+
+    let m = p.start();
+    p.bump();
+
+    while !p.at(TokenKind::RBrace) && !p.at_end() {
+        stmt::stmt(p);
+    }
+
+    p.expect(TokenKind::RBrace);
+
+    m.complete(p, SyntaxKind::BlockExpr)
+}
+
+fn if_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::IfKw));
+
+    let m = p.start();
+    p.bump();
+
+    expr(p);
+
+    if p.at(TokenKind::LBrace) {
+        block_expr(p);
+    } else {
+        p.error();
+    }
+
+    if p.at(TokenKind::ElseKw) {
+        p.bump();
+
+        if p.at(TokenKind::IfKw) {
+            if_expr(p);
+        } else if p.at(TokenKind::LBrace) {
+            block_expr(p);
+        } else {
+            p.error();
+        }
+    }
+
+    m.complete(p, SyntaxKind::IfExpr)
+}
+
 fn prefix_expr(p: &mut Parser) -> CompletedMarker {
-    assert!(p.at(TokenKind::Minus));
+    assert!(p.at(TokenKind::Minus) || p.at(TokenKind::Bang) || p.at(TokenKind::Tilde));
 
     let m = p.start();
 
-    let op = UnaryOp::Neg;
+    let op = if p.at(TokenKind::Minus) {
+        UnaryOp::Neg
+    } else if p.at(TokenKind::Bang) {
+        UnaryOp::Not
+    } else {
+        UnaryOp::BitNot
+    };
     let ((), right_binding_power) = op.binding_power();
 
     // Eat the operator’s token.
@@ -316,15 +506,831 @@ Root@0..35
         check(
             "(1+",
             expect![[r#"
-Root@0..3
-  ParenExpr@0..3
-    LParen@0..1 "("
-    InfixExpr@1..3
-      Literal@1..2
-        Number@1..2 "1"
-      Plus@2..3 "+"
-error at 2..3: expected number, identifier, ‘-’ or ‘(’
-error at 2..3: expected ‘)’"#]],
+                Root@0..3
+                  ParenExpr@0..3
+                    LParen@0..1 "("
+                    InfixExpr@1..3
+                      Literal@1..2
+                        Number@1..2 "1"
+                      Plus@2..3 "+"
+                error at 2..3: expected number, ‘true’, ‘false’, identifier, ‘-’, ‘!’, ‘~’, ‘(’, ‘{’ or ‘if’
+                error at 2..3: expected ‘)’"#]],
+        );
+    }
+
+    #[test]
+    fn parse_call() {
+        check(
+            "add(1, 2 * 3)",
+            expect![[r#"
+                Root@0..13
+                  CallExpr@0..13
+                    VariableRef@0..3
+                      Ident@0..3 "add"
+                    ArgList@3..13
+                      LParen@3..4 "("
+                      Literal@4..5
+                        Number@4..5 "1"
+                      Comma@5..6 ","
+                      Whitespace@6..7 " "
+                      InfixExpr@7..12
+                        Literal@7..9
+                          Number@7..8 "2"
+                          Whitespace@8..9 " "
+                        Star@9..10 "*"
+                        Whitespace@10..11 " "
+                        Literal@11..12
+                          Number@11..12 "3"
+                      RParen@12..13 ")""#]],
+        );
+    }
+
+    #[test]
+    fn parse_call_without_args() {
+        check(
+            "f()",
+            expect![[r#"
+                Root@0..3
+                  CallExpr@0..3
+                    VariableRef@0..1
+                      Ident@0..1 "f"
+                    ArgList@1..3
+                      LParen@1..2 "("
+                      RParen@2..3 ")""#]],
+        );
+    }
+
+    #[test]
+    fn parse_call_in_infix_expression() {
+        check(
+            "1 + f(x) * 2",
+            expect![[r#"
+                Root@0..12
+                  InfixExpr@0..12
+                    Literal@0..2
+                      Number@0..1 "1"
+                      Whitespace@1..2 " "
+                    Plus@2..3 "+"
+                    Whitespace@3..4 " "
+                    InfixExpr@4..12
+                      CallExpr@4..9
+                        VariableRef@4..5
+                          Ident@4..5 "f"
+                        ArgList@5..9
+                          LParen@5..6 "("
+                          VariableRef@6..7
+                            Ident@6..7 "x"
+                          RParen@7..8 ")"
+                          Whitespace@8..9 " "
+                      Star@9..10 "*"
+                      Whitespace@10..11 " "
+                      Literal@11..12
+                        Number@11..12 "2""#]],
+        );
+    }
+
+    #[test]
+    fn parse_nested_call() {
+        check(
+            "f(g(1),)",
+            expect![[r#"
+                Root@0..8
+                  CallExpr@0..8
+                    VariableRef@0..1
+                      Ident@0..1 "f"
+                    ArgList@1..8
+                      LParen@1..2 "("
+                      CallExpr@2..6
+                        VariableRef@2..3
+                          Ident@2..3 "g"
+                        ArgList@3..6
+                          LParen@3..4 "("
+                          Literal@4..5
+                            Number@4..5 "1"
+                          RParen@5..6 ")"
+                      Comma@6..7 ","
+                      RParen@7..8 ")""#]],
+        );
+    }
+
+    #[test]
+    fn parse_unclosed_call() {
+        check(
+            "f(1",
+            expect![[r#"
+                Root@0..3
+                  CallExpr@0..3
+                    VariableRef@0..1
+                      Ident@0..1 "f"
+                    ArgList@1..3
+                      LParen@1..2 "("
+                      Literal@2..3
+                        Number@2..3 "1"
+                error at 2..3: expected ‘+’, ‘-’, ‘*’, ‘/’, ‘%’, ‘**’, ‘&’, ‘|’, ‘^’, ‘<<’, ‘>>’, ‘==’, ‘!=’, ‘<’, ‘<=’, ‘>’, ‘>=’, ‘&&’, ‘||’, ‘=’, ‘+=’, ‘-=’, ‘*=’, ‘/=’ or ‘)’"#]],
+        );
+    }
+
+    #[test]
+    fn parse_block_expr() {
+        check(
+            "{ let a = 1\n a }",
+            expect![[r#"
+                Root@0..16
+                  BlockExpr@0..16
+                    LBrace@0..1 "{"
+                    Whitespace@1..2 " "
+                    VariableDef@2..13
+                      LetKw@2..5 "let"
+                      Whitespace@5..6 " "
+                      Ident@6..7 "a"
+                      Whitespace@7..8 " "
+                      Equals@8..9 "="
+                      Whitespace@9..10 " "
+                      Literal@10..13
+                        Number@10..11 "1"
+                        Whitespace@11..13 "\n "
+                    VariableRef@13..15
+                      Ident@13..14 "a"
+                      Whitespace@14..15 " "
+                    RBrace@15..16 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_empty_block_expr() {
+        check(
+            "{}",
+            expect![[r#"
+                Root@0..2
+                  BlockExpr@0..2
+                    LBrace@0..1 "{"
+                    RBrace@1..2 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_block_expr_in_infix_expression() {
+        check(
+            "1 + { 2 } * 3",
+            expect![[r#"
+                Root@0..13
+                  InfixExpr@0..13
+                    Literal@0..2
+                      Number@0..1 "1"
+                      Whitespace@1..2 " "
+                    Plus@2..3 "+"
+                    Whitespace@3..4 " "
+                    InfixExpr@4..13
+                      BlockExpr@4..10
+                        LBrace@4..5 "{"
+                        Whitespace@5..6 " "
+                        Literal@6..8
+                          Number@6..7 "2"
+                          Whitespace@7..8 " "
+                        RBrace@8..9 "}"
+                        Whitespace@9..10 " "
+                      Star@10..11 "*"
+                      Whitespace@11..12 " "
+                      Literal@12..13
+                        Number@12..13 "3""#]],
+        );
+    }
+
+    #[test]
+    fn parse_nested_block_exprs() {
+        check(
+            "{ { 1 } }",
+            expect![[r#"
+                Root@0..9
+                  BlockExpr@0..9
+                    LBrace@0..1 "{"
+                    Whitespace@1..2 " "
+                    BlockExpr@2..8
+                      LBrace@2..3 "{"
+                      Whitespace@3..4 " "
+                      Literal@4..6
+                        Number@4..5 "1"
+                        Whitespace@5..6 " "
+                      RBrace@6..7 "}"
+                      Whitespace@7..8 " "
+                    RBrace@8..9 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_unclosed_block_expr() {
+        check(
+            "{ 1",
+            expect![[r#"
+                Root@0..3
+                  BlockExpr@0..3
+                    LBrace@0..1 "{"
+                    Whitespace@1..2 " "
+                    Literal@2..3
+                      Number@2..3 "1"
+                error at 2..3: expected ‘+’, ‘-’, ‘*’, ‘/’, ‘%’, ‘**’, ‘&’, ‘|’, ‘^’, ‘<<’, ‘>>’, ‘==’, ‘!=’, ‘<’, ‘<=’, ‘>’, ‘>=’, ‘&&’, ‘||’, ‘=’, ‘+=’, ‘-=’, ‘*=’, ‘/=’ or ‘}’"#]],
+        );
+    }
+
+    #[test]
+    fn parse_boolean_literals() {
+        check(
+            "true + false",
+            expect![[r#"
+                Root@0..12
+                  InfixExpr@0..12
+                    Literal@0..5
+                      TrueKw@0..4 "true"
+                      Whitespace@4..5 " "
+                    Plus@5..6 "+"
+                    Whitespace@6..7 " "
+                    Literal@7..12
+                      FalseKw@7..12 "false""#]],
+        );
+    }
+
+    #[test]
+    fn parse_if_expr() {
+        check(
+            "if a { 1 } else { 2 }",
+            expect![[r#"
+                Root@0..21
+                  IfExpr@0..21
+                    IfKw@0..2 "if"
+                    Whitespace@2..3 " "
+                    VariableRef@3..5
+                      Ident@3..4 "a"
+                      Whitespace@4..5 " "
+                    BlockExpr@5..11
+                      LBrace@5..6 "{"
+                      Whitespace@6..7 " "
+                      Literal@7..9
+                        Number@7..8 "1"
+                        Whitespace@8..9 " "
+                      RBrace@9..10 "}"
+                      Whitespace@10..11 " "
+                    ElseKw@11..15 "else"
+                    Whitespace@15..16 " "
+                    BlockExpr@16..21
+                      LBrace@16..17 "{"
+                      Whitespace@17..18 " "
+                      Literal@18..20
+                        Number@18..19 "2"
+                        Whitespace@19..20 " "
+                      RBrace@20..21 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_if_expr_without_else() {
+        check(
+            "if true { 1 }",
+            expect![[r#"
+                Root@0..13
+                  IfExpr@0..13
+                    IfKw@0..2 "if"
+                    Whitespace@2..3 " "
+                    Literal@3..8
+                      TrueKw@3..7 "true"
+                      Whitespace@7..8 " "
+                    BlockExpr@8..13
+                      LBrace@8..9 "{"
+                      Whitespace@9..10 " "
+                      Literal@10..12
+                        Number@10..11 "1"
+                        Whitespace@11..12 " "
+                      RBrace@12..13 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_else_if() {
+        check(
+            "if a { 1 } else if b { 2 } else { 3 }",
+            expect![[r#"
+                Root@0..37
+                  IfExpr@0..37
+                    IfKw@0..2 "if"
+                    Whitespace@2..3 " "
+                    VariableRef@3..5
+                      Ident@3..4 "a"
+                      Whitespace@4..5 " "
+                    BlockExpr@5..11
+                      LBrace@5..6 "{"
+                      Whitespace@6..7 " "
+                      Literal@7..9
+                        Number@7..8 "1"
+                        Whitespace@8..9 " "
+                      RBrace@9..10 "}"
+                      Whitespace@10..11 " "
+                    ElseKw@11..15 "else"
+                    Whitespace@15..16 " "
+                    IfExpr@16..37
+                      IfKw@16..18 "if"
+                      Whitespace@18..19 " "
+                      VariableRef@19..21
+                        Ident@19..20 "b"
+                        Whitespace@20..21 " "
+                      BlockExpr@21..27
+                        LBrace@21..22 "{"
+                        Whitespace@22..23 " "
+                        Literal@23..25
+                          Number@23..24 "2"
+                          Whitespace@24..25 " "
+                        RBrace@25..26 "}"
+                        Whitespace@26..27 " "
+                      ElseKw@27..31 "else"
+                      Whitespace@31..32 " "
+                      BlockExpr@32..37
+                        LBrace@32..33 "{"
+                        Whitespace@33..34 " "
+                        Literal@34..36
+                          Number@34..35 "3"
+                          Whitespace@35..36 " "
+                        RBrace@36..37 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_if_expr_in_infix_expression() {
+        check(
+            "1 + if a { 2 } else { 3 }",
+            expect![[r#"
+                Root@0..25
+                  InfixExpr@0..25
+                    Literal@0..2
+                      Number@0..1 "1"
+                      Whitespace@1..2 " "
+                    Plus@2..3 "+"
+                    Whitespace@3..4 " "
+                    IfExpr@4..25
+                      IfKw@4..6 "if"
+                      Whitespace@6..7 " "
+                      VariableRef@7..9
+                        Ident@7..8 "a"
+                        Whitespace@8..9 " "
+                      BlockExpr@9..15
+                        LBrace@9..10 "{"
+                        Whitespace@10..11 " "
+                        Literal@11..13
+                          Number@11..12 "2"
+                          Whitespace@12..13 " "
+                        RBrace@13..14 "}"
+                        Whitespace@14..15 " "
+                      ElseKw@15..19 "else"
+                      Whitespace@19..20 " "
+                      BlockExpr@20..25
+                        LBrace@20..21 "{"
+                        Whitespace@21..22 " "
+                        Literal@22..24
+                          Number@22..23 "3"
+                          Whitespace@23..24 " "
+                        RBrace@24..25 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_if_expr_without_block() {
+        check(
+            "if a 1",
+            expect![[r#"
+                Root@0..6
+                  IfExpr@0..6
+                    IfKw@0..2 "if"
+                    Whitespace@2..3 " "
+                    VariableRef@3..5
+                      Ident@3..4 "a"
+                      Whitespace@4..5 " "
+                    Error@5..6
+                      Number@5..6 "1"
+                error at 5..6: expected ‘(’, ‘+’, ‘-’, ‘*’, ‘/’, ‘%’, ‘**’, ‘&’, ‘|’, ‘^’, ‘<<’, ‘>>’, ‘==’, ‘!=’, ‘<’, ‘<=’, ‘>’, ‘>=’, ‘&&’, ‘||’, ‘=’, ‘+=’, ‘-=’, ‘*=’, ‘/=’ or ‘{’, but found number"#]],
+        );
+    }
+
+    #[test]
+    fn parse_if_expr_with_missing_else_branch() {
+        check(
+            "if a { 1 } else",
+            expect![[r#"
+                Root@0..15
+                  IfExpr@0..15
+                    IfKw@0..2 "if"
+                    Whitespace@2..3 " "
+                    VariableRef@3..5
+                      Ident@3..4 "a"
+                      Whitespace@4..5 " "
+                    BlockExpr@5..11
+                      LBrace@5..6 "{"
+                      Whitespace@6..7 " "
+                      Literal@7..9
+                        Number@7..8 "1"
+                        Whitespace@8..9 " "
+                      RBrace@9..10 "}"
+                      Whitespace@10..11 " "
+                    ElseKw@11..15 "else"
+                error at 11..15: expected ‘if’ or ‘{’"#]],
+        );
+    }
+
+    #[test]
+    fn parse_comparison() {
+        check(
+            "a <= b",
+            expect![[r#"
+                Root@0..6
+                  InfixExpr@0..6
+                    VariableRef@0..2
+                      Ident@0..1 "a"
+                      Whitespace@1..2 " "
+                    LessEquals@2..4 "<="
+                    Whitespace@4..5 " "
+                    VariableRef@5..6
+                      Ident@5..6 "b""#]],
+        );
+    }
+
+    #[test]
+    fn comparison_binds_looser_than_arithmetic() {
+        check(
+            "1 + 2 == 3",
+            expect![[r#"
+                Root@0..10
+                  InfixExpr@0..10
+                    InfixExpr@0..6
+                      Literal@0..2
+                        Number@0..1 "1"
+                        Whitespace@1..2 " "
+                      Plus@2..3 "+"
+                      Whitespace@3..4 " "
+                      Literal@4..6
+                        Number@4..5 "2"
+                        Whitespace@5..6 " "
+                    EqualsEquals@6..8 "=="
+                    Whitespace@8..9 " "
+                    Literal@9..10
+                      Number@9..10 "3""#]],
+        );
+    }
+
+    #[test]
+    fn and_binds_tighter_than_or() {
+        check(
+            "a || b && c",
+            expect![[r#"
+                Root@0..11
+                  InfixExpr@0..11
+                    VariableRef@0..2
+                      Ident@0..1 "a"
+                      Whitespace@1..2 " "
+                    PipePipe@2..4 "||"
+                    Whitespace@4..5 " "
+                    InfixExpr@5..11
+                      VariableRef@5..7
+                        Ident@5..6 "b"
+                        Whitespace@6..7 " "
+                      AmpAmp@7..9 "&&"
+                      Whitespace@9..10 " "
+                      VariableRef@10..11
+                        Ident@10..11 "c""#]],
+        );
+    }
+
+    #[test]
+    fn logical_operators_bind_looser_than_comparison() {
+        check(
+            "a < b && c != d",
+            expect![[r#"
+                Root@0..15
+                  InfixExpr@0..15
+                    InfixExpr@0..6
+                      VariableRef@0..2
+                        Ident@0..1 "a"
+                        Whitespace@1..2 " "
+                      Less@2..3 "<"
+                      Whitespace@3..4 " "
+                      VariableRef@4..6
+                        Ident@4..5 "b"
+                        Whitespace@5..6 " "
+                    AmpAmp@6..8 "&&"
+                    Whitespace@8..9 " "
+                    InfixExpr@9..15
+                      VariableRef@9..11
+                        Ident@9..10 "c"
+                        Whitespace@10..11 " "
+                      BangEquals@11..13 "!="
+                      Whitespace@13..14 " "
+                      VariableRef@14..15
+                        Ident@14..15 "d""#]],
+        );
+    }
+
+    #[test]
+    fn parse_not() {
+        check(
+            "!a == b",
+            expect![[r#"
+                Root@0..7
+                  InfixExpr@0..7
+                    PrefixExpr@0..3
+                      Bang@0..1 "!"
+                      VariableRef@1..3
+                        Ident@1..2 "a"
+                        Whitespace@2..3 " "
+                    EqualsEquals@3..5 "=="
+                    Whitespace@5..6 " "
+                    VariableRef@6..7
+                      Ident@6..7 "b""#]],
+        );
+    }
+
+    #[test]
+    fn parse_double_not() {
+        check(
+            "!!true",
+            expect![[r#"
+                Root@0..6
+                  PrefixExpr@0..6
+                    Bang@0..1 "!"
+                    PrefixExpr@1..6
+                      Bang@1..2 "!"
+                      Literal@2..6
+                        TrueKw@2..6 "true""#]],
+        );
+    }
+
+    #[test]
+    fn parse_remainder() {
+        check(
+            "1 + 7 % 3",
+            expect![[r#"
+                Root@0..9
+                  InfixExpr@0..9
+                    Literal@0..2
+                      Number@0..1 "1"
+                      Whitespace@1..2 " "
+                    Plus@2..3 "+"
+                    Whitespace@3..4 " "
+                    InfixExpr@4..9
+                      Literal@4..6
+                        Number@4..5 "7"
+                        Whitespace@5..6 " "
+                      Percent@6..7 "%"
+                      Whitespace@7..8 " "
+                      Literal@8..9
+                        Number@8..9 "3""#]],
+        );
+    }
+
+    #[test]
+    fn exponent_is_right_associative() {
+        check(
+            "2 ** 3 ** 2",
+            expect![[r#"
+                Root@0..11
+                  InfixExpr@0..11
+                    Literal@0..2
+                      Number@0..1 "2"
+                      Whitespace@1..2 " "
+                    StarStar@2..4 "**"
+                    Whitespace@4..5 " "
+                    InfixExpr@5..11
+                      Literal@5..7
+                        Number@5..6 "3"
+                        Whitespace@6..7 " "
+                      StarStar@7..9 "**"
+                      Whitespace@9..10 " "
+                      Literal@10..11
+                        Number@10..11 "2""#]],
+        );
+    }
+
+    #[test]
+    fn exponent_binds_tighter_than_negation() {
+        check(
+            "-2 ** 2",
+            expect![[r#"
+                Root@0..7
+                  PrefixExpr@0..7
+                    Minus@0..1 "-"
+                    InfixExpr@1..7
+                      Literal@1..3
+                        Number@1..2 "2"
+                        Whitespace@2..3 " "
+                      StarStar@3..5 "**"
+                      Whitespace@5..6 " "
+                      Literal@6..7
+                        Number@6..7 "2""#]],
+        );
+    }
+
+    #[test]
+    fn exponent_with_negated_rhs() {
+        check(
+            "2 ** -1",
+            expect![[r#"
+                Root@0..7
+                  InfixExpr@0..7
+                    Literal@0..2
+                      Number@0..1 "2"
+                      Whitespace@1..2 " "
+                    StarStar@2..4 "**"
+                    Whitespace@4..5 " "
+                    PrefixExpr@5..7
+                      Minus@5..6 "-"
+                      Literal@6..7
+                        Number@6..7 "1""#]],
+        );
+    }
+
+    #[test]
+    fn bitwise_operator_precedence() {
+        check(
+            "a | b ^ c & d << 1",
+            expect![[r#"
+                Root@0..18
+                  InfixExpr@0..18
+                    VariableRef@0..2
+                      Ident@0..1 "a"
+                      Whitespace@1..2 " "
+                    Pipe@2..3 "|"
+                    Whitespace@3..4 " "
+                    InfixExpr@4..18
+                      VariableRef@4..6
+                        Ident@4..5 "b"
+                        Whitespace@5..6 " "
+                      Caret@6..7 "^"
+                      Whitespace@7..8 " "
+                      InfixExpr@8..18
+                        VariableRef@8..10
+                          Ident@8..9 "c"
+                          Whitespace@9..10 " "
+                        Amp@10..11 "&"
+                        Whitespace@11..12 " "
+                        InfixExpr@12..18
+                          VariableRef@12..14
+                            Ident@12..13 "d"
+                            Whitespace@13..14 " "
+                          LessLess@14..16 "<<"
+                          Whitespace@16..17 " "
+                          Literal@17..18
+                            Number@17..18 "1""#]],
+        );
+    }
+
+    #[test]
+    fn bitwise_operators_bind_tighter_than_comparison() {
+        check(
+            "a & 1 == 0",
+            expect![[r#"
+                Root@0..10
+                  InfixExpr@0..10
+                    InfixExpr@0..6
+                      VariableRef@0..2
+                        Ident@0..1 "a"
+                        Whitespace@1..2 " "
+                      Amp@2..3 "&"
+                      Whitespace@3..4 " "
+                      Literal@4..6
+                        Number@4..5 "1"
+                        Whitespace@5..6 " "
+                    EqualsEquals@6..8 "=="
+                    Whitespace@8..9 " "
+                    Literal@9..10
+                      Number@9..10 "0""#]],
+        );
+    }
+
+    #[test]
+    fn parse_bitwise_not() {
+        check(
+            "~a >> 2",
+            expect![[r#"
+                Root@0..7
+                  InfixExpr@0..7
+                    PrefixExpr@0..3
+                      Tilde@0..1 "~"
+                      VariableRef@1..3
+                        Ident@1..2 "a"
+                        Whitespace@2..3 " "
+                    GreaterGreater@3..5 ">>"
+                    Whitespace@5..6 " "
+                    Literal@6..7
+                      Number@6..7 "2""#]],
+        );
+    }
+
+    #[test]
+    fn parse_assignment() {
+        check(
+            "a = 1 + 2",
+            expect![[r#"
+                Root@0..9
+                  AssignExpr@0..9
+                    VariableRef@0..2
+                      Ident@0..1 "a"
+                      Whitespace@1..2 " "
+                    Equals@2..3 "="
+                    Whitespace@3..4 " "
+                    InfixExpr@4..9
+                      Literal@4..6
+                        Number@4..5 "1"
+                        Whitespace@5..6 " "
+                      Plus@6..7 "+"
+                      Whitespace@7..8 " "
+                      Literal@8..9
+                        Number@8..9 "2""#]],
+        );
+    }
+
+    #[test]
+    fn assignment_is_right_associative() {
+        check(
+            "a = b = c",
+            expect![[r#"
+                Root@0..9
+                  AssignExpr@0..9
+                    VariableRef@0..2
+                      Ident@0..1 "a"
+                      Whitespace@1..2 " "
+                    Equals@2..3 "="
+                    Whitespace@3..4 " "
+                    AssignExpr@4..9
+                      VariableRef@4..6
+                        Ident@4..5 "b"
+                        Whitespace@5..6 " "
+                      Equals@6..7 "="
+                      Whitespace@7..8 " "
+                      VariableRef@8..9
+                        Ident@8..9 "c""#]],
+        );
+    }
+
+    #[test]
+    fn assignment_binds_looser_than_logical_operators() {
+        check(
+            "a = b || c",
+            expect![[r#"
+                Root@0..10
+                  AssignExpr@0..10
+                    VariableRef@0..2
+                      Ident@0..1 "a"
+                      Whitespace@1..2 " "
+                    Equals@2..3 "="
+                    Whitespace@3..4 " "
+                    InfixExpr@4..10
+                      VariableRef@4..6
+                        Ident@4..5 "b"
+                        Whitespace@5..6 " "
+                      PipePipe@6..8 "||"
+                      Whitespace@8..9 " "
+                      VariableRef@9..10
+                        Ident@9..10 "c""#]],
+        );
+    }
+
+    #[test]
+    fn parse_compound_assignment() {
+        check(
+            "a += b *= 2",
+            expect![[r#"
+                Root@0..11
+                  AssignExpr@0..11
+                    VariableRef@0..2
+                      Ident@0..1 "a"
+                      Whitespace@1..2 " "
+                    PlusEquals@2..4 "+="
+                    Whitespace@4..5 " "
+                    AssignExpr@5..11
+                      VariableRef@5..7
+                        Ident@5..6 "b"
+                        Whitespace@6..7 " "
+                      StarEquals@7..9 "*="
+                      Whitespace@9..10 " "
+                      Literal@10..11
+                        Number@10..11 "2""#]],
+        );
+    }
+
+    #[test]
+    fn parse_assignment_to_non_variable() {
+        check(
+            "1 + a = 2",
+            expect![[r#"
+                Root@0..9
+                  AssignExpr@0..9
+                    InfixExpr@0..6
+                      Literal@0..2
+                        Number@0..1 "1"
+                        Whitespace@1..2 " "
+                      Plus@2..3 "+"
+                      Whitespace@3..4 " "
+                      VariableRef@4..6
+                        Ident@4..5 "a"
+                        Whitespace@5..6 " "
+                    Equals@6..7 "="
+                    Whitespace@7..8 " "
+                    Literal@8..9
+                      Number@8..9 "2""#]],
         );
     }
 
@@ -414,12 +1420,12 @@ Root@0..7
         check(
             "(foo",
             expect![[r#"
-Root@0..4
-  ParenExpr@0..4
-    LParen@0..1 "("
-    VariableRef@1..4
-      Ident@1..4 "foo"
-error at 1..4: expected ‘+’, ‘-’, ‘*’, ‘/’ or ‘)’"#]],
+                Root@0..4
+                  ParenExpr@0..4
+                    LParen@0..1 "("
+                    VariableRef@1..4
+                      Ident@1..4 "foo"
+                error at 1..4: expected ‘(’, ‘+’, ‘-’, ‘*’, ‘/’, ‘%’, ‘**’, ‘&’, ‘|’, ‘^’, ‘<<’, ‘>>’, ‘==’, ‘!=’, ‘<’, ‘<=’, ‘>’, ‘>=’, ‘&&’, ‘||’, ‘=’, ‘+=’, ‘-=’, ‘*=’, ‘/=’ or ‘)’"#]],
         );
     }
 }
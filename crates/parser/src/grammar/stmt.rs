@@ -3,6 +3,14 @@ use super::*;
 pub(super) fn stmt(p: &mut Parser) -> Option<CompletedMarker> {
     if p.at(TokenKind::LetKw) {
         Some(variable_def(p))
+    } else if p.at(TokenKind::FnKw) {
+        Some(fn_def(p))
+    } else if p.at(TokenKind::WhileKw) {
+        Some(while_loop(p))
+    } else if p.at(TokenKind::BreakKw) {
+        Some(keyword_stmt(p, SyntaxKind::Break))
+    } else if p.at(TokenKind::ContinueKw) {
+        Some(keyword_stmt(p, SyntaxKind::Continue))
     } else {
         expr::expr(p)
     }
@@ -13,7 +21,16 @@ fn variable_def(p: &mut Parser) -> CompletedMarker {
     let m = p.start();
     p.bump();
 
+    if p.at(TokenKind::MutKw) {
+        p.bump();
+    }
+
     p.expect(TokenKind::Ident);
+
+    if p.at(TokenKind::Colon) {
+        type_annotation(p);
+    }
+
     p.expect(TokenKind::Equals);
 
     expr::expr(p);
@@ -21,6 +38,84 @@ fn variable_def(p: &mut Parser) -> CompletedMarker {
     m.complete(p, SyntaxKind::VariableDef)
 }
 
+fn fn_def(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::FnKw));
+    let m = p.start();
+    p.bump();
+
+    p.expect(TokenKind::Ident);
+
+    if p.at(TokenKind::LParen) {
+        param_list(p);
+    } else {
+        p.error();
+    }
+
+    if p.at(TokenKind::LBrace) {
+        expr::block_expr(p);
+    } else {
+        p.error();
+    }
+
+    m.complete(p, SyntaxKind::FnDef)
+}
+
+fn param_list(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LParen));
+    let m = p.start();
+    p.bump();
+
+    while p.at(TokenKind::Ident) {
+        let param = p.start();
+        p.bump();
+
+        if p.at(TokenKind::Colon) {
+            type_annotation(p);
+        }
+
+        param.complete(p, SyntaxKind::Param);
+
+        if !p.at(TokenKind::RParen) {
+            p.expect(TokenKind::Comma);
+        }
+    }
+
+    p.expect(TokenKind::RParen);
+
+    m.complete(p, SyntaxKind::ParamList)
+}
+
+/// Parses the `: Type` that can follow the name of a variable or parameter.
+fn type_annotation(p: &mut Parser) {
+    assert!(p.at(TokenKind::Colon));
+    p.bump();
+
+    type_ref::type_ref(p);
+}
+
+fn while_loop(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::WhileKw));
+    let m = p.start();
+    p.bump();
+
+    expr::expr(p);
+
+    if p.at(TokenKind::LBrace) {
+        expr::block_expr(p);
+    } else {
+        p.error();
+    }
+
+    m.complete(p, SyntaxKind::WhileLoop)
+}
+
+/// Parses a statement that consists of nothing but a keyword, such as `break`.
+fn keyword_stmt(p: &mut Parser, kind: SyntaxKind) -> CompletedMarker {
+    let m = p.start();
+    p.bump();
+    m.complete(p, kind)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::check;
@@ -44,29 +139,291 @@ Root@0..13
         );
     }
 
+    #[test]
+    fn parse_mutable_variable_def() {
+        check(
+            "let mut a = 1",
+            expect![[r#"
+                Root@0..13
+                  VariableDef@0..13
+                    LetKw@0..3 "let"
+                    Whitespace@3..4 " "
+                    MutKw@4..7 "mut"
+                    Whitespace@7..8 " "
+                    Ident@8..9 "a"
+                    Whitespace@9..10 " "
+                    Equals@10..11 "="
+                    Whitespace@11..12 " "
+                    Literal@12..13
+                      Number@12..13 "1""#]],
+        );
+    }
+
+    #[test]
+    fn parse_fn_def() {
+        check(
+            "fn add(x, y) { x + y }",
+            expect![[r#"
+                Root@0..22
+                  FnDef@0..22
+                    FnKw@0..2 "fn"
+                    Whitespace@2..3 " "
+                    Ident@3..6 "add"
+                    ParamList@6..13
+                      LParen@6..7 "("
+                      Param@7..8
+                        Ident@7..8 "x"
+                      Comma@8..9 ","
+                      Whitespace@9..10 " "
+                      Param@10..11
+                        Ident@10..11 "y"
+                      RParen@11..12 ")"
+                      Whitespace@12..13 " "
+                    BlockExpr@13..22
+                      LBrace@13..14 "{"
+                      Whitespace@14..15 " "
+                      InfixExpr@15..21
+                        VariableRef@15..17
+                          Ident@15..16 "x"
+                          Whitespace@16..17 " "
+                        Plus@17..18 "+"
+                        Whitespace@18..19 " "
+                        VariableRef@19..21
+                          Ident@19..20 "y"
+                          Whitespace@20..21 " "
+                      RBrace@21..22 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_fn_def_without_params() {
+        check(
+            "fn ten() {\n  let a = 5\n  a * 2\n}",
+            expect![[r#"
+                Root@0..32
+                  FnDef@0..32
+                    FnKw@0..2 "fn"
+                    Whitespace@2..3 " "
+                    Ident@3..6 "ten"
+                    ParamList@6..9
+                      LParen@6..7 "("
+                      RParen@7..8 ")"
+                      Whitespace@8..9 " "
+                    BlockExpr@9..32
+                      LBrace@9..10 "{"
+                      Whitespace@10..13 "\n  "
+                      VariableDef@13..25
+                        LetKw@13..16 "let"
+                        Whitespace@16..17 " "
+                        Ident@17..18 "a"
+                        Whitespace@18..19 " "
+                        Equals@19..20 "="
+                        Whitespace@20..21 " "
+                        Literal@21..25
+                          Number@21..22 "5"
+                          Whitespace@22..25 "\n  "
+                      InfixExpr@25..31
+                        VariableRef@25..27
+                          Ident@25..26 "a"
+                          Whitespace@26..27 " "
+                        Star@27..28 "*"
+                        Whitespace@28..29 " "
+                        Literal@29..31
+                          Number@29..30 "2"
+                          Whitespace@30..31 "\n"
+                      RBrace@31..32 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_fn_def_with_trailing_comma() {
+        check(
+            "fn id(x,) { x }",
+            expect![[r#"
+                Root@0..15
+                  FnDef@0..15
+                    FnKw@0..2 "fn"
+                    Whitespace@2..3 " "
+                    Ident@3..5 "id"
+                    ParamList@5..10
+                      LParen@5..6 "("
+                      Param@6..7
+                        Ident@6..7 "x"
+                      Comma@7..8 ","
+                      RParen@8..9 ")"
+                      Whitespace@9..10 " "
+                    BlockExpr@10..15
+                      LBrace@10..11 "{"
+                      Whitespace@11..12 " "
+                      VariableRef@12..14
+                        Ident@12..13 "x"
+                        Whitespace@13..14 " "
+                      RBrace@14..15 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_fn_def_with_missing_comma() {
+        check(
+            "fn add(x y) { x }",
+            expect![[r#"
+                Root@0..17
+                  FnDef@0..17
+                    FnKw@0..2 "fn"
+                    Whitespace@2..3 " "
+                    Ident@3..6 "add"
+                    ParamList@6..12
+                      LParen@6..7 "("
+                      Param@7..9
+                        Ident@7..8 "x"
+                        Whitespace@8..9 " "
+                      Error@9..10
+                        Ident@9..10 "y"
+                      RParen@10..11 ")"
+                      Whitespace@11..12 " "
+                    BlockExpr@12..17
+                      LBrace@12..13 "{"
+                      Whitespace@13..14 " "
+                      VariableRef@14..16
+                        Ident@14..15 "x"
+                        Whitespace@15..16 " "
+                      RBrace@16..17 "}"
+                error at 9..10: expected ‘:’, ‘)’ or ‘,’, but found identifier"#]],
+        );
+    }
+
+    #[test]
+    fn parse_unclosed_fn_body() {
+        check(
+            "fn f() { 1",
+            expect![[r#"
+                Root@0..10
+                  FnDef@0..10
+                    FnKw@0..2 "fn"
+                    Whitespace@2..3 " "
+                    Ident@3..4 "f"
+                    ParamList@4..7
+                      LParen@4..5 "("
+                      RParen@5..6 ")"
+                      Whitespace@6..7 " "
+                    BlockExpr@7..10
+                      LBrace@7..8 "{"
+                      Whitespace@8..9 " "
+                      Literal@9..10
+                        Number@9..10 "1"
+                error at 9..10: expected ‘+’, ‘-’, ‘*’, ‘/’, ‘%’, ‘**’, ‘&’, ‘|’, ‘^’, ‘<<’, ‘>>’, ‘==’, ‘!=’, ‘<’, ‘<=’, ‘>’, ‘>=’, ‘&&’, ‘||’, ‘=’, ‘+=’, ‘-=’, ‘*=’, ‘/=’ or ‘}’"#]],
+        );
+    }
+
+    #[test]
+    fn recover_on_fn_token() {
+        check(
+            "let a =\nfn f() {}",
+            expect![[r#"
+                Root@0..17
+                  VariableDef@0..8
+                    LetKw@0..3 "let"
+                    Whitespace@3..4 " "
+                    Ident@4..5 "a"
+                    Whitespace@5..6 " "
+                    Equals@6..7 "="
+                    Whitespace@7..8 "\n"
+                  FnDef@8..17
+                    FnKw@8..10 "fn"
+                    Whitespace@10..11 " "
+                    Ident@11..12 "f"
+                    ParamList@12..15
+                      LParen@12..13 "("
+                      RParen@13..14 ")"
+                      Whitespace@14..15 " "
+                    BlockExpr@15..17
+                      LBrace@15..16 "{"
+                      RBrace@16..17 "}"
+                error at 8..10: expected number, ‘true’, ‘false’, identifier, ‘-’, ‘!’, ‘~’, ‘(’, ‘{’ or ‘if’, but found ‘fn’"#]],
+        );
+    }
+
+    #[test]
+    fn parse_while_loop() {
+        check(
+            "while a { break\ncontinue }",
+            expect![[r#"
+                Root@0..26
+                  WhileLoop@0..26
+                    WhileKw@0..5 "while"
+                    Whitespace@5..6 " "
+                    VariableRef@6..8
+                      Ident@6..7 "a"
+                      Whitespace@7..8 " "
+                    BlockExpr@8..26
+                      LBrace@8..9 "{"
+                      Whitespace@9..10 " "
+                      Break@10..16
+                        BreakKw@10..15 "break"
+                        Whitespace@15..16 "\n"
+                      Continue@16..25
+                        ContinueKw@16..24 "continue"
+                        Whitespace@24..25 " "
+                      RBrace@25..26 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_while_loop_without_body() {
+        check(
+            "while a",
+            expect![[r#"
+                Root@0..7
+                  WhileLoop@0..7
+                    WhileKw@0..5 "while"
+                    Whitespace@5..6 " "
+                    VariableRef@6..7
+                      Ident@6..7 "a"
+                error at 6..7: expected ‘(’, ‘+’, ‘-’, ‘*’, ‘/’, ‘%’, ‘**’, ‘&’, ‘|’, ‘^’, ‘<<’, ‘>>’, ‘==’, ‘!=’, ‘<’, ‘<=’, ‘>’, ‘>=’, ‘&&’, ‘||’, ‘=’, ‘+=’, ‘-=’, ‘*=’, ‘/=’ or ‘{’"#]],
+        );
+    }
+
+    #[test]
+    fn parse_break_in_expression() {
+        check(
+            "1 + break",
+            expect![[r#"
+                Root@0..9
+                  InfixExpr@0..4
+                    Literal@0..2
+                      Number@0..1 "1"
+                      Whitespace@1..2 " "
+                    Plus@2..3 "+"
+                    Whitespace@3..4 " "
+                  Break@4..9
+                    BreakKw@4..9 "break"
+                error at 4..9: expected number, ‘true’, ‘false’, identifier, ‘-’, ‘!’, ‘~’, ‘(’, ‘{’ or ‘if’, but found ‘break’"#]],
+        );
+    }
+
     #[test]
     fn recover_on_let_token() {
         check(
             "let a =\nlet b = a",
             expect![[r#"
-Root@0..17
-  VariableDef@0..8
-    LetKw@0..3 "let"
-    Whitespace@3..4 " "
-    Ident@4..5 "a"
-    Whitespace@5..6 " "
-    Equals@6..7 "="
-    Whitespace@7..8 "\n"
-  VariableDef@8..17
-    LetKw@8..11 "let"
-    Whitespace@11..12 " "
-    Ident@12..13 "b"
-    Whitespace@13..14 " "
-    Equals@14..15 "="
-    Whitespace@15..16 " "
-    VariableRef@16..17
-      Ident@16..17 "a"
-error at 8..11: expected number, identifier, ‘-’ or ‘(’, but found ‘let’"#]],
+                Root@0..17
+                  VariableDef@0..8
+                    LetKw@0..3 "let"
+                    Whitespace@3..4 " "
+                    Ident@4..5 "a"
+                    Whitespace@5..6 " "
+                    Equals@6..7 "="
+                    Whitespace@7..8 "\n"
+                  VariableDef@8..17
+                    LetKw@8..11 "let"
+                    Whitespace@11..12 " "
+                    Ident@12..13 "b"
+                    Whitespace@13..14 " "
+                    Equals@14..15 "="
+                    Whitespace@15..16 " "
+                    VariableRef@16..17
+                      Ident@16..17 "a"
+                error at 8..11: expected number, ‘true’, ‘false’, identifier, ‘-’, ‘!’, ‘~’, ‘(’, ‘{’ or ‘if’, but found ‘let’"#]],
         );
     }
 }
@@ -0,0 +1,93 @@
+use super::*;
+
+pub(super) fn type_ref(p: &mut Parser) -> Option<CompletedMarker> {
+    if p.at(TokenKind::Ident) {
+        let m = p.start();
+        p.bump();
+        Some(m.complete(p, SyntaxKind::TypeRef))
+    } else {
+        p.error();
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check;
+    use expect_test::expect;
+
+    #[test]
+    fn parse_variable_def_with_type() {
+        check(
+            "let a: Int = 1",
+            expect![[r#"
+                Root@0..14
+                  VariableDef@0..14
+                    LetKw@0..3 "let"
+                    Whitespace@3..4 " "
+                    Ident@4..5 "a"
+                    Colon@5..6 ":"
+                    Whitespace@6..7 " "
+                    TypeRef@7..11
+                      Ident@7..10 "Int"
+                      Whitespace@10..11 " "
+                    Equals@11..12 "="
+                    Whitespace@12..13 " "
+                    Literal@13..14
+                      Number@13..14 "1""#]],
+        );
+    }
+
+    #[test]
+    fn parse_params_with_types() {
+        check(
+            "fn f(x: Int, y) {}",
+            expect![[r#"
+                Root@0..18
+                  FnDef@0..18
+                    FnKw@0..2 "fn"
+                    Whitespace@2..3 " "
+                    Ident@3..4 "f"
+                    ParamList@4..16
+                      LParen@4..5 "("
+                      Param@5..11
+                        Ident@5..6 "x"
+                        Colon@6..7 ":"
+                        Whitespace@7..8 " "
+                        TypeRef@8..11
+                          Ident@8..11 "Int"
+                      Comma@11..12 ","
+                      Whitespace@12..13 " "
+                      Param@13..14
+                        Ident@13..14 "y"
+                      RParen@14..15 ")"
+                      Whitespace@15..16 " "
+                    BlockExpr@16..18
+                      LBrace@16..17 "{"
+                      RBrace@17..18 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_variable_def_with_missing_type() {
+        check(
+            "let a: = 1",
+            expect![[r#"
+                Root@0..10
+                  VariableDef@0..10
+                    LetKw@0..3 "let"
+                    Whitespace@3..4 " "
+                    Ident@4..5 "a"
+                    Colon@5..6 ":"
+                    Whitespace@6..7 " "
+                    Error@7..9
+                      Equals@7..8 "="
+                      Whitespace@8..9 " "
+                    Error@9..10
+                      Number@9..10 "1"
+                error at 7..8: expected identifier, but found ‘=’
+                error at 9..10: expected ‘=’, but found number
+                error at 9..10: expected number, ‘true’, ‘false’, identifier, ‘-’, ‘!’, ‘~’, ‘(’, ‘{’ or ‘if’"#]],
+        );
+    }
+}
@@ -5,6 +5,7 @@ mod sink;
 mod source;
 
 use crate::parser::{ParseError, Parser};
+use diagnostics::Diagnostic;
 use lexer::Lexer;
 use rowan::GreenNode;
 use sink::Sink;
@@ -45,6 +46,16 @@ impl Parse {
     pub fn syntax(&self) -> SyntaxNode {
         SyntaxNode::new_root(self.green_node.clone())
     }
+
+    pub fn errors(&self) -> Vec<Diagnostic> {
+        self.errors.iter().map(ParseError::to_diagnostic).collect()
+    }
+
+    /// Returns whether parsing failed only because the input ended too early, meaning that more
+    /// input could make it parse successfully.
+    pub fn is_incomplete(&self) -> bool {
+        !self.errors.is_empty() && self.errors.iter().all(|error| error.found.is_none())
+    }
 }
 
 #[cfg(test)]
@@ -52,3 +63,42 @@ fn check(input: &str, expected_tree: expect_test::Expect) {
     let parse = parse(input);
     expected_tree.assert_eq(&parse.debug_tree());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_incomplete(input: &str, is_incomplete: bool) {
+        assert_eq!(parse(input).is_incomplete(), is_incomplete);
+    }
+
+    #[test]
+    fn complete_expr_is_not_incomplete() {
+        check_incomplete("1 + 2", false);
+    }
+
+    #[test]
+    fn empty_input_is_not_incomplete() {
+        check_incomplete("", false);
+    }
+
+    #[test]
+    fn unclosed_parenthesis_is_incomplete() {
+        check_incomplete("(1 +\n2", true);
+    }
+
+    #[test]
+    fn trailing_binary_operator_is_incomplete() {
+        check_incomplete("1 *", true);
+    }
+
+    #[test]
+    fn variable_def_without_value_is_incomplete() {
+        check_incomplete("let x =", true);
+    }
+
+    #[test]
+    fn unexpected_token_is_not_incomplete() {
+        check_incomplete("let = 10", false);
+    }
+}
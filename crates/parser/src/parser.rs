@@ -11,7 +11,13 @@ use marker::Marker;
 use std::mem;
 use syntax::SyntaxKind;
 
-const RECOVERY_SET: [TokenKind; 1] = [TokenKind::LetKw];
+const RECOVERY_SET: [TokenKind; 5] = [
+    TokenKind::LetKw,
+    TokenKind::FnKw,
+    TokenKind::WhileKw,
+    TokenKind::BreakKw,
+    TokenKind::ContinueKw,
+];
 
 pub(crate) struct Parser<'t, 'input> {
     source: Source<'t, 'input>,
@@ -79,12 +85,17 @@ impl<'t, 'input> Parser<'t, 'input> {
     }
 
     pub(crate) fn at(&mut self, kind: TokenKind) -> bool {
-        self.expected_kinds.push(kind);
+        // A kind is often checked for more than once before the next token is bumped, such as in a
+        // loop condition and again after the loop, but should only be listed as expected once.
+        if !self.expected_kinds.contains(&kind) {
+            self.expected_kinds.push(kind);
+        }
+
         self.peek() == Some(kind)
     }
 
     fn at_set(&mut self, set: &[TokenKind]) -> bool {
-        self.peek().map_or(false, |k| set.contains(&k))
+        self.peek().is_some_and(|k| set.contains(&k))
     }
 
     pub(crate) fn at_end(&mut self) -> bool {
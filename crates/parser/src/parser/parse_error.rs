@@ -1,3 +1,4 @@
+use diagnostics::Diagnostic;
 use lexer::TokenKind;
 use std::fmt;
 use text_size::TextRange;
@@ -5,24 +6,53 @@ use text_size::TextRange;
 #[derive(Debug, PartialEq)]
 pub(crate) struct ParseError {
     pub(super) expected: Vec<TokenKind>,
-    pub(super) found: Option<TokenKind>,
+    pub(crate) found: Option<TokenKind>,
     pub(super) range: TextRange,
 }
 
+impl ParseError {
+    pub(crate) fn to_diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::error("E0001", Message(self).to_string(), self.range);
+
+        if self.found.is_some() {
+            return diagnostic;
+        }
+
+        let diagnostic = diagnostic.with_note("the end of the input was reached");
+
+        // If the input ended before a delimiter was closed, we know exactly how to fix it.
+        if self.expected.contains(&TokenKind::RParen) {
+            let end = TextRange::empty(self.range.end());
+            diagnostic.with_suggestion("insert ‘)’", end, ")")
+        } else {
+            diagnostic
+        }
+    }
+}
+
 impl fmt::Display for ParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "error at {}..{}: expected ",
+            "error at {}..{}: {}",
             u32::from(self.range.start()),
             u32::from(self.range.end()),
-        )?;
+            Message(self),
+        )
+    }
+}
+
+struct Message<'a>(&'a ParseError);
 
-        let num_expected = self.expected.len();
+impl fmt::Display for Message<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected ")?;
+
+        let num_expected = self.0.expected.len();
         let is_first = |idx| idx == 0;
         let is_last = |idx| idx == num_expected - 1;
 
-        for (idx, expected_kind) in self.expected.iter().enumerate() {
+        for (idx, expected_kind) in self.0.expected.iter().enumerate() {
             if is_first(idx) {
                 write!(f, "{}", expected_kind)?;
             } else if is_last(idx) {
@@ -32,7 +62,7 @@ impl fmt::Display for ParseError {
             }
         }
 
-        if let Some(found) = self.found {
+        if let Some(found) = self.0.found {
             write!(f, ", but found {}", found)?;
         }
 
@@ -94,6 +124,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn diagnostic_at_end_of_input() {
+        let error = ParseError {
+            expected: vec![TokenKind::Ident],
+            found: None,
+            range: TextRange::new(1.into(), 2.into()),
+        };
+
+        assert_eq!(
+            error.to_diagnostic(),
+            Diagnostic::error("E0001", "expected identifier", error.range)
+                .with_note("the end of the input was reached"),
+        );
+    }
+
+    #[test]
+    fn diagnostic_for_unclosed_parenthesis_suggests_closing_it() {
+        let error = ParseError {
+            expected: vec![TokenKind::Plus, TokenKind::RParen],
+            found: None,
+            range: TextRange::new(1.into(), 2.into()),
+        };
+
+        assert_eq!(
+            error.to_diagnostic(),
+            Diagnostic::error("E0001", "expected ‘+’ or ‘)’", error.range)
+                .with_note("the end of the input was reached")
+                .with_suggestion("insert ‘)’", TextRange::empty(2.into()), ")"),
+        );
+    }
+
     #[test]
     fn multiple_expected_did_find() {
         check(
@@ -25,7 +25,7 @@ impl<'t, 'input> Source<'t, 'input> {
         self.peek_kind_raw()
     }
 
-    pub(crate) fn peek_token(&mut self) -> Option<&Token> {
+    pub(crate) fn peek_token(&mut self) -> Option<&Token<'input>> {
         self.eat_trivia();
         self.peek_token_raw()
     }
@@ -37,7 +37,7 @@ impl<'t, 'input> Source<'t, 'input> {
     }
 
     fn at_trivia(&self) -> bool {
-        self.peek_kind_raw().map_or(false, TokenKind::is_trivia)
+        self.peek_kind_raw().is_some_and(TokenKind::is_trivia)
     }
 
     pub(crate) fn last_token_range(&self) -> Option<TextRange> {
@@ -48,7 +48,7 @@ impl<'t, 'input> Source<'t, 'input> {
         self.peek_token_raw().map(|Token { kind, .. }| *kind)
     }
 
-    fn peek_token_raw(&self) -> Option<&Token> {
+    fn peek_token_raw(&self) -> Option<&Token<'input>> {
         self.tokens.get(self.cursor)
     }
 }
@@ -1,19 +1,51 @@
 use lexer::TokenKind;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
+use rowan::TextRange;
 
-#[derive(Debug, Copy, Clone, PartialEq, FromPrimitive, ToPrimitive)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, FromPrimitive, ToPrimitive)]
 pub enum SyntaxKind {
     Whitespace,
     FnKw,
     LetKw,
+    MutKw,
+    IfKw,
+    ElseKw,
+    TrueKw,
+    FalseKw,
+    WhileKw,
+    BreakKw,
+    ContinueKw,
     Ident,
     Number,
     Plus,
     Minus,
     Star,
     Slash,
+    Percent,
+    StarStar,
+    Amp,
+    Pipe,
+    Caret,
+    LessLess,
+    GreaterGreater,
+    Tilde,
     Equals,
+    PlusEquals,
+    MinusEquals,
+    StarEquals,
+    SlashEquals,
+    EqualsEquals,
+    BangEquals,
+    Less,
+    LessEquals,
+    Greater,
+    GreaterEquals,
+    AmpAmp,
+    PipePipe,
+    Bang,
+    Comma,
+    Colon,
     LParen,
     RParen,
     LBrace,
@@ -21,12 +53,30 @@ pub enum SyntaxKind {
     Comment,
     Error,
     Root,
+    ArgList,
+    AssignExpr,
+    BlockExpr,
+    Break,
+    CallExpr,
+    Continue,
+    FnDef,
+    IfExpr,
     InfixExpr,
     Literal,
+    Param,
+    ParamList,
     ParenExpr,
     PrefixExpr,
+    TypeRef,
     VariableDef,
     VariableRef,
+    WhileLoop,
+}
+
+impl SyntaxKind {
+    pub fn is_trivia(self) -> bool {
+        matches!(self, Self::Whitespace | Self::Comment)
+    }
 }
 
 impl From<TokenKind> for SyntaxKind {
@@ -35,13 +85,44 @@ impl From<TokenKind> for SyntaxKind {
             TokenKind::Whitespace => Self::Whitespace,
             TokenKind::FnKw => Self::FnKw,
             TokenKind::LetKw => Self::LetKw,
+            TokenKind::MutKw => Self::MutKw,
+            TokenKind::IfKw => Self::IfKw,
+            TokenKind::ElseKw => Self::ElseKw,
+            TokenKind::TrueKw => Self::TrueKw,
+            TokenKind::FalseKw => Self::FalseKw,
+            TokenKind::WhileKw => Self::WhileKw,
+            TokenKind::BreakKw => Self::BreakKw,
+            TokenKind::ContinueKw => Self::ContinueKw,
             TokenKind::Ident => Self::Ident,
             TokenKind::Number => Self::Number,
             TokenKind::Plus => Self::Plus,
             TokenKind::Minus => Self::Minus,
             TokenKind::Star => Self::Star,
             TokenKind::Slash => Self::Slash,
+            TokenKind::Percent => Self::Percent,
+            TokenKind::StarStar => Self::StarStar,
+            TokenKind::Amp => Self::Amp,
+            TokenKind::Pipe => Self::Pipe,
+            TokenKind::Caret => Self::Caret,
+            TokenKind::LessLess => Self::LessLess,
+            TokenKind::GreaterGreater => Self::GreaterGreater,
+            TokenKind::Tilde => Self::Tilde,
             TokenKind::Equals => Self::Equals,
+            TokenKind::PlusEquals => Self::PlusEquals,
+            TokenKind::MinusEquals => Self::MinusEquals,
+            TokenKind::StarEquals => Self::StarEquals,
+            TokenKind::SlashEquals => Self::SlashEquals,
+            TokenKind::EqualsEquals => Self::EqualsEquals,
+            TokenKind::BangEquals => Self::BangEquals,
+            TokenKind::Less => Self::Less,
+            TokenKind::LessEquals => Self::LessEquals,
+            TokenKind::Greater => Self::Greater,
+            TokenKind::GreaterEquals => Self::GreaterEquals,
+            TokenKind::AmpAmp => Self::AmpAmp,
+            TokenKind::PipePipe => Self::PipePipe,
+            TokenKind::Bang => Self::Bang,
+            TokenKind::Comma => Self::Comma,
+            TokenKind::Colon => Self::Colon,
             TokenKind::LParen => Self::LParen,
             TokenKind::RParen => Self::RParen,
             TokenKind::LBrace => Self::LBrace,
@@ -56,6 +137,44 @@ pub type SyntaxNode = rowan::SyntaxNode<EldiroLanguage>;
 pub type SyntaxElement = rowan::SyntaxElement<EldiroLanguage>;
 pub type SyntaxToken = rowan::SyntaxToken<EldiroLanguage>;
 
+/// Identifies a node in a syntax tree without keeping the tree alive, so that it can be stored
+/// alongside data derived from the tree. The node can be found again given the tree’s root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SyntaxNodePtr {
+    kind: SyntaxKind,
+    range: TextRange,
+}
+
+impl SyntaxNodePtr {
+    pub fn new(node: &SyntaxNode) -> Self {
+        Self {
+            kind: node.kind(),
+            range: node.text_range(),
+        }
+    }
+
+    pub fn kind(self) -> SyntaxKind {
+        self.kind
+    }
+
+    /// The range of the node, including any whitespace and comments at its start and end.
+    pub fn range(self) -> TextRange {
+        self.range
+    }
+
+    /// Finds the node this points to in the tree with the given root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node isn’t in that tree.
+    pub fn to_node(self, root: &SyntaxNode) -> SyntaxNode {
+        root.covering_element(self.range)
+            .ancestors()
+            .find(|node| node.kind() == self.kind && node.text_range() == self.range)
+            .unwrap_or_else(|| panic!("{:?} is not in this tree", self))
+    }
+}
+
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum EldiroLanguage {}
 
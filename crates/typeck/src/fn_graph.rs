@@ -0,0 +1,180 @@
+use hir::{BindingIdx, Database, Expr, ExprIdx, FnIdx, Stmt};
+
+/// Which of the functions defined in one list of statements use each other. A function uses
+/// another if its body refers to it, including from inside functions nested in the body.
+pub(crate) struct FnGraph {
+    uses: Vec<Vec<usize>>,
+}
+
+impl FnGraph {
+    pub(crate) fn new(db: &Database, fns: &[(BindingIdx, FnIdx)]) -> Self {
+        let uses = fns
+            .iter()
+            .map(|(_, func)| {
+                let mut refs = Vec::new();
+                collect_refs(db, db[*func].body, &mut refs);
+
+                fns.iter()
+                    .enumerate()
+                    .filter(|(_, (binding, _))| refs.contains(binding))
+                    .map(|(idx, _)| idx)
+                    .collect()
+            })
+            .collect();
+
+        Self { uses }
+    }
+
+    /// Splits the functions into groups of mutually recursive functions, ordered so that each
+    /// group only uses functions from itself and from the groups before it.
+    pub(crate) fn groups(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            uses: &self.uses,
+            index: vec![None; self.uses.len()],
+            low_link: vec![0; self.uses.len()],
+            stack: Vec::new(),
+            on_stack: vec![false; self.uses.len()],
+            next_index: 0,
+            groups: Vec::new(),
+        };
+
+        for func in 0..self.uses.len() {
+            if tarjan.index[func].is_none() {
+                tarjan.visit(func);
+            }
+        }
+
+        tarjan.groups
+    }
+
+    /// Returns, for each function, the smallest of `positions` out of the function itself and
+    /// every function that uses it, directly or indirectly.
+    pub(crate) fn first_users(&self, positions: &[usize]) -> Vec<usize> {
+        let mut first_users = vec![usize::MAX; self.uses.len()];
+
+        let mut by_position: Vec<_> = (0..self.uses.len()).collect();
+        by_position.sort_by_key(|func| positions[*func]);
+
+        // Whichever function reaches another first has the smallest position of all the
+        // functions that use it, so every function only has to be visited once.
+        for user in by_position {
+            let mut stack = vec![user];
+
+            while let Some(func) = stack.pop() {
+                if first_users[func] == usize::MAX {
+                    first_users[func] = positions[user];
+                    stack.extend(&self.uses[func]);
+                }
+            }
+        }
+
+        first_users
+    }
+}
+
+/// Tarjan’s algorithm for finding strongly connected components, which finds them in an order
+/// where each one only has edges to itself and to those before it.
+struct Tarjan<'a> {
+    uses: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low_link: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    next_index: usize,
+    groups: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, func: usize) {
+        self.index[func] = Some(self.next_index);
+        self.low_link[func] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(func);
+        self.on_stack[func] = true;
+
+        for &used in &self.uses[func] {
+            match self.index[used] {
+                None => {
+                    self.visit(used);
+                    self.low_link[func] = self.low_link[func].min(self.low_link[used]);
+                }
+                Some(index) if self.on_stack[used] => {
+                    self.low_link[func] = self.low_link[func].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low_link[func]) == self.index[func] {
+            let mut group = Vec::new();
+
+            loop {
+                let member = self.stack.pop().unwrap();
+                self.on_stack[member] = false;
+                group.push(member);
+
+                if member == func {
+                    break;
+                }
+            }
+
+            group.sort_unstable();
+            self.groups.push(group);
+        }
+    }
+}
+
+/// Adds every binding referred to by `expr` to `refs`.
+fn collect_refs(db: &Database, expr: ExprIdx, refs: &mut Vec<BindingIdx>) {
+    match &db[expr] {
+        Expr::Missing | Expr::Bool { .. } | Expr::Literal { .. } => {}
+        Expr::Assign { binding, value, .. } => {
+            refs.push(*binding);
+            collect_refs(db, *value, refs);
+        }
+        Expr::Binary { lhs, rhs, .. } => {
+            collect_refs(db, *lhs, refs);
+            collect_refs(db, *rhs, refs);
+        }
+        Expr::Block { stmts, tail } => {
+            for stmt in stmts {
+                match stmt {
+                    Stmt::Break | Stmt::Continue => {}
+                    Stmt::FnDef { func, .. } => collect_refs(db, db[*func].body, refs),
+                    Stmt::VariableDef { value, .. } | Stmt::Expr(value) => {
+                        collect_refs(db, *value, refs)
+                    }
+                    Stmt::While { condition, body } => {
+                        collect_refs(db, *condition, refs);
+                        collect_refs(db, *body, refs);
+                    }
+                }
+            }
+
+            if let Some(tail) = tail {
+                collect_refs(db, *tail, refs);
+            }
+        }
+        Expr::Call { callee, args } => {
+            collect_refs(db, *callee, refs);
+
+            for arg in args {
+                collect_refs(db, *arg, refs);
+            }
+        }
+        Expr::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            collect_refs(db, *condition, refs);
+            collect_refs(db, *then_branch, refs);
+
+            if let Some(else_branch) = else_branch {
+                collect_refs(db, *else_branch, refs);
+            }
+        }
+        Expr::Unary { expr, .. } => collect_refs(db, *expr, refs),
+        Expr::VariableRef { binding } => refs.push(*binding),
+    }
+}
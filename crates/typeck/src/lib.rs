@@ -0,0 +1,876 @@
+mod fn_graph;
+mod table;
+mod ty;
+mod type_error;
+
+pub use ty::{Ty, TyVar};
+
+use diagnostics::Diagnostic;
+use fn_graph::FnGraph;
+use hir::{BinaryOp, BindingIdx, Database, Expr, ExprIdx, FnIdx, Stmt, UnaryOp};
+use la_arena::ArenaMap;
+use std::mem;
+use table::{Scheme, Table};
+use text_size::TextRange;
+use type_error::{TypeError, TypeErrorKind};
+
+/// Infers the type of every expression and binding, keeping what it has learnt about global
+/// bindings so that later inputs can be checked in the context of earlier ones.
+#[derive(Debug, Clone, Default)]
+pub struct TypeChecker {
+    table: Table,
+    expr_types: ArenaMap<ExprIdx, Ty>,
+    binding_types: ArenaMap<BindingIdx, Scheme>,
+    /// How many function definitions enclose the code being checked. Type variables created
+    /// inside a function can be generalized once the function has been checked, unless they were
+    /// unified with types from further out.
+    level: u32,
+    /// Literals without a suffix and negations from the input being checked. Whether they are
+    /// valid depends on which integer types they have, which may not be known until the whole
+    /// input has been checked.
+    literals: Vec<ExprIdx>,
+    negations: Vec<ExprIdx>,
+    errors: Vec<TypeError>,
+}
+
+impl TypeChecker {
+    /// Checks `stmts`, which must have been lowered into `db`, returning any type errors found.
+    pub fn check_stmts(&mut self, db: &Database, stmts: &[Stmt]) -> Vec<Diagnostic> {
+        self.check_stmt_list(db, stmts);
+        // Integer types that nothing determined default to `Int`, so every integer type is
+        // known from here on.
+        self.table.default_int_vars();
+        self.check_int_exprs(db);
+
+        mem::take(&mut self.errors)
+            .into_iter()
+            .map(TypeError::into_diagnostic)
+            .collect()
+    }
+
+    /// Returns the type inferred for `expr`, or `Ty::Unknown` if it hasn’t been checked. Type
+    /// variables are left in the types of functions that accept arguments of any type.
+    pub fn type_of(&self, expr: ExprIdx) -> Ty {
+        self.expr_types
+            .get(expr)
+            .map_or(Ty::Unknown, |ty| self.table.resolve(ty))
+    }
+
+    /// Returns the type inferred for `binding`, or `Ty::Unknown` if it hasn’t been checked.
+    pub fn type_of_binding(&self, binding: BindingIdx) -> Ty {
+        self.binding_types
+            .get(binding)
+            .map_or(Ty::Unknown, |scheme| self.table.resolve(scheme.ty()))
+    }
+
+    fn check_stmt_list(&mut self, db: &Database, stmts: &[Stmt]) {
+        // Functions can be used before they are defined, so they are all given a type up front
+        // from their parameters’ annotations.
+        let (positions, fns): (Vec<_>, Vec<_>) = stmts
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, stmt)| match stmt {
+                Stmt::FnDef { binding, func } => Some((idx, (*binding, *func))),
+                _ => None,
+            })
+            .unzip();
+
+        for (binding, func) in &fns {
+            let ty = self.declare_fn(db, *func);
+            self.binding_types.insert(*binding, Scheme::mono(ty));
+        }
+
+        // A function can only be generalized once it and every function it uses have been
+        // checked, since until then its type may still be constrained by their bodies. Each group
+        // of mutually recursive functions is checked and generalized just before the first
+        // statement after it, or after a function that uses it, so that statement can use it at
+        // more than one type.
+        let graph = FnGraph::new(db, &fns);
+        let first_users = graph.first_users(&positions);
+        let mut groups = graph.groups();
+
+        for (idx, stmt) in stmts.iter().enumerate() {
+            if matches!(stmt, Stmt::FnDef { .. }) {
+                continue;
+            }
+
+            let (due, rest) = groups
+                .into_iter()
+                .partition(|group| first_users[group[0]] < idx);
+            groups = rest;
+
+            for group in due {
+                self.check_fn_group(db, &fns, &group);
+            }
+
+            self.check_stmt(db, stmt);
+        }
+
+        for group in groups {
+            self.check_fn_group(db, &fns, &group);
+        }
+    }
+
+    fn check_fn_group(&mut self, db: &Database, fns: &[(BindingIdx, FnIdx)], group: &[usize]) {
+        for &idx in group {
+            let (binding, func) = fns[idx];
+            self.check_fn_def(db, binding, func);
+        }
+
+        for &idx in group {
+            let (binding, _) = fns[idx];
+            let scheme = self
+                .table
+                .generalize(self.binding_types[binding].ty(), self.level);
+            self.binding_types.insert(binding, scheme);
+        }
+    }
+
+    fn check_stmt(&mut self, db: &Database, stmt: &Stmt) {
+        match stmt {
+            // Functions are checked by `check_stmt_list` in the order they use each other.
+            Stmt::Break | Stmt::Continue | Stmt::FnDef { .. } => {}
+            Stmt::VariableDef {
+                binding,
+                type_ref,
+                value,
+            } => {
+                let ty = match type_ref {
+                    Some(type_ref) => {
+                        let ty = Ty::from(*type_ref);
+                        self.expect_expr(db, *value, &ty);
+                        ty
+                    }
+                    None => self.infer_expr(db, *value),
+                };
+
+                self.binding_types.insert(*binding, Scheme::mono(ty));
+            }
+            Stmt::While { condition, body } => {
+                self.expect_expr(db, *condition, &Ty::Bool);
+                self.infer_expr(db, *body);
+            }
+            Stmt::Expr(expr) => {
+                self.infer_expr(db, *expr);
+            }
+        }
+    }
+
+    /// Gives a function its type before its body is checked. Parameters without an annotation
+    /// get type variables that are created inside the function so that they can be generalized.
+    fn declare_fn(&mut self, db: &Database, func: FnIdx) -> Ty {
+        let level = self.level + 1;
+
+        let params = db[func]
+            .params
+            .iter()
+            .map(|param| match param.type_ref {
+                Some(type_ref) => Ty::from(type_ref),
+                None => self.table.new_var(level),
+            })
+            .collect();
+
+        Ty::Fn {
+            params,
+            ret: Box::new(self.table.new_var(level)),
+        }
+    }
+
+    fn check_fn_def(&mut self, db: &Database, binding: BindingIdx, func: FnIdx) {
+        let func = &db[func];
+
+        // Any uses of the function so far have been unified with the type it was declared with,
+        // so that errors are reported at those uses or inside the body rather than at the
+        // definition.
+        let (params, ret) = match self.table.shallow_resolve(self.binding_types[binding].ty()) {
+            Ty::Fn { params, ret } => (params, *ret),
+            _ => unreachable!(),
+        };
+
+        for (param, ty) in func.params.iter().zip(params) {
+            self.binding_types.insert(param.binding, Scheme::mono(ty));
+        }
+
+        self.level += 1;
+        self.expect_expr(db, func.body, &ret);
+        self.level -= 1;
+    }
+
+    fn infer_expr(&mut self, db: &Database, expr: ExprIdx) -> Ty {
+        let ty = match &db[expr] {
+            Expr::Missing => Ty::Unknown,
+            Expr::Assign { binding, op, value } => {
+                let binding_ty = self.type_of_binding_use(*binding);
+
+                if op.is_some() {
+                    let int_ty = self.table.new_int_var(self.level);
+
+                    if self.table.unify(&binding_ty, &int_ty).is_err() {
+                        let kind = TypeErrorKind::Mismatch {
+                            expected: self.table.resolve(&int_ty),
+                            found: self.table.resolve(&binding_ty),
+                        };
+                        self.error(db.source_map().expr_range(expr), kind);
+                    }
+                }
+
+                self.expect_expr(db, *value, &binding_ty);
+
+                Ty::Unit
+            }
+            Expr::Binary { op, lhs, rhs } => self.infer_binary(db, op, *lhs, *rhs),
+            Expr::Block { stmts, tail } => {
+                self.check_stmt_list(db, stmts);
+
+                match tail {
+                    Some(tail) => self.infer_expr(db, *tail),
+                    None => Ty::Unit,
+                }
+            }
+            Expr::Bool { .. } => Ty::Bool,
+            Expr::Call { callee, args } => self.infer_call(db, expr, *callee, args),
+            Expr::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.expect_expr(db, *condition, &Ty::Bool);
+
+                match else_branch {
+                    Some(else_branch) => {
+                        let ty = self.infer_expr(db, *then_branch);
+                        self.expect_expr(db, *else_branch, &ty);
+                        ty
+                    }
+                    // Without an `else` there is no value when the condition is false.
+                    None => {
+                        self.expect_expr(db, *then_branch, &Ty::Unit);
+                        Ty::Unit
+                    }
+                }
+            }
+            Expr::Literal { suffix, .. } => match suffix {
+                Some(ty) => Ty::Int(*ty),
+                None => {
+                    self.literals.push(expr);
+                    self.table.new_int_var(self.level)
+                }
+            },
+            Expr::Unary { op, expr: operand } => {
+                let ty = match op {
+                    UnaryOp::Neg => {
+                        self.negations.push(expr);
+                        self.table.new_int_var(self.level)
+                    }
+                    UnaryOp::BitNot => self.table.new_int_var(self.level),
+                    UnaryOp::Not => Ty::Bool,
+                };
+                self.expect_expr(db, *operand, &ty);
+                ty
+            }
+            Expr::VariableRef { binding } => self.type_of_binding_use(*binding),
+        };
+
+        self.expr_types.insert(expr, ty.clone());
+
+        ty
+    }
+
+    fn infer_binary(&mut self, db: &Database, op: &BinaryOp, lhs: ExprIdx, rhs: ExprIdx) -> Ty {
+        match op {
+            BinaryOp::And | BinaryOp::Or => {
+                self.expect_expr(db, lhs, &Ty::Bool);
+                self.expect_expr(db, rhs, &Ty::Bool);
+                Ty::Bool
+            }
+            BinaryOp::Eq | BinaryOp::NotEq => {
+                let ty = self.infer_expr(db, lhs);
+                self.expect_expr(db, rhs, &ty);
+                Ty::Bool
+            }
+            BinaryOp::Less | BinaryOp::LessEq | BinaryOp::Greater | BinaryOp::GreaterEq => {
+                let ty = self.table.new_int_var(self.level);
+                self.expect_expr(db, lhs, &ty);
+                self.expect_expr(db, rhs, &ty);
+                Ty::Bool
+            }
+            _ => {
+                let ty = self.table.new_int_var(self.level);
+                self.expect_expr(db, lhs, &ty);
+                self.expect_expr(db, rhs, &ty);
+                ty
+            }
+        }
+    }
+
+    fn infer_call(
+        &mut self,
+        db: &Database,
+        call: ExprIdx,
+        callee: ExprIdx,
+        args: &[ExprIdx],
+    ) -> Ty {
+        let callee_ty = self.infer_expr(db, callee);
+
+        let (params, ret) = match self.table.shallow_resolve(&callee_ty) {
+            Ty::Fn { params, ret } => (params, *ret),
+            Ty::Var(_) => {
+                let params: Vec<_> = args
+                    .iter()
+                    .map(|_| self.table.new_var(self.level))
+                    .collect();
+                let ret = self.table.new_var(self.level);
+                let fn_ty = Ty::Fn {
+                    params: params.clone(),
+                    ret: Box::new(ret.clone()),
+                };
+                self.table.unify(&callee_ty, &fn_ty).unwrap();
+
+                (params, ret)
+            }
+            Ty::Unknown => (vec![Ty::Unknown; args.len()], Ty::Unknown),
+            ty => {
+                let kind = TypeErrorKind::NotCallable {
+                    found: self.table.resolve(&ty),
+                };
+                self.error(db.source_map().expr_range(callee), kind);
+
+                (vec![Ty::Unknown; args.len()], Ty::Unknown)
+            }
+        };
+
+        if params.len() != args.len() {
+            let kind = TypeErrorKind::WrongArgumentCount {
+                expected: params.len(),
+                found: args.len(),
+            };
+            self.error(db.source_map().expr_range(call), kind);
+
+            for arg in args {
+                self.infer_expr(db, *arg);
+            }
+        } else {
+            for (param, arg) in params.iter().zip(args) {
+                self.expect_expr(db, *arg, param);
+            }
+        }
+
+        ret
+    }
+
+    /// Infers the type of `expr`, reporting an error if it isn’t `expected`.
+    fn expect_expr(&mut self, db: &Database, expr: ExprIdx, expected: &Ty) {
+        let ty = self.infer_expr(db, expr);
+
+        if self.table.unify(expected, &ty).is_err() {
+            let kind = TypeErrorKind::Mismatch {
+                expected: self.table.resolve(expected),
+                found: self.table.resolve(&ty),
+            };
+            self.error(db.source_map().expr_range(expr), kind);
+        }
+    }
+
+    /// Reports negations of unsigned integers, and literals without a suffix that don’t fit in
+    /// the type inferred for them.
+    fn check_int_exprs(&mut self, db: &Database) {
+        let mut negated_literals = Vec::new();
+
+        for negation in mem::take(&mut self.negations) {
+            let operand = match db[negation] {
+                Expr::Unary { expr, .. } => expr,
+                _ => unreachable!(),
+            };
+
+            if let Expr::Literal { .. } = db[operand] {
+                negated_literals.push(operand);
+            }
+
+            if let Ty::Int(ty) = self.type_of(operand) {
+                if !ty.is_signed() {
+                    let kind = TypeErrorKind::CannotNegate { found: Ty::Int(ty) };
+                    self.error(db.source_map().expr_range(negation), kind);
+                }
+            }
+        }
+
+        for literal in mem::take(&mut self.literals) {
+            let n = match &db[literal] {
+                Expr::Literal { n, .. } => n,
+                _ => unreachable!(),
+            };
+
+            if let Ty::Int(ty) = self.type_of(literal) {
+                let negated = negated_literals.contains(&literal);
+
+                if ty.literal_value(n, negated).is_none() {
+                    let kind = TypeErrorKind::LiteralOutOfRange { ty };
+                    self.error(db.source_map().expr_range(literal), kind);
+                }
+            }
+        }
+    }
+
+    /// Returns the type of a use of `binding`. Uses of generic functions each get their own copy
+    /// of the function’s type variables.
+    fn type_of_binding_use(&mut self, binding: BindingIdx) -> Ty {
+        match self.binding_types.get(binding) {
+            Some(scheme) => {
+                let scheme = scheme.clone();
+                self.table.instantiate(&scheme, self.level)
+            }
+            // Global bindings from earlier inputs that failed to lower may not have been given a
+            // type.
+            None => {
+                let ty = self.table.new_var(0);
+                self.binding_types.insert(binding, Scheme::mono(ty.clone()));
+                ty
+            }
+        }
+    }
+
+    /// Records an error at `range`. Only expressions and bindings that were lowered from the
+    /// current input have a range, and every error is about one of those, so `range` is always
+    /// present in practice.
+    fn error(&mut self, range: Option<TextRange>, kind: TypeErrorKind) {
+        if let Some(range) = range {
+            self.errors.push(TypeError { kind, range });
+        }
+    }
+}
+
+/// Type checks `stmts`, which must have been lowered into `db`.
+pub fn check(db: &Database, stmts: &[Stmt]) -> (TypeChecker, Vec<Diagnostic>) {
+    let mut type_checker = TypeChecker::default();
+    let diagnostics = type_checker.check_stmts(db, stmts);
+
+    (type_checker, diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hir::IntTy;
+
+    fn range(start: u32, end: u32) -> TextRange {
+        TextRange::new(start.into(), end.into())
+    }
+
+    fn lower(db: &mut Database, input: &str) -> Vec<Stmt> {
+        let root = ast::Root::cast(parser::parse(input).syntax()).unwrap();
+        db.lower_root(root).0
+    }
+
+    fn check(input: &str) -> (Database, Vec<Stmt>, TypeChecker, Vec<Diagnostic>) {
+        let mut db = Database::default();
+        let stmts = lower(&mut db, input);
+        let (type_checker, diagnostics) = super::check(&db, &stmts);
+
+        (db, stmts, type_checker, diagnostics)
+    }
+
+    /// Checks that `input`, whose last statement is an expression, has no errors and that the
+    /// expression has type `expected`.
+    fn check_ty(input: &str, expected: Ty) {
+        let (_, stmts, type_checker, diagnostics) = check(input);
+
+        let expr = match stmts.last() {
+            Some(Stmt::Expr(expr)) => *expr,
+            _ => unreachable!(),
+        };
+
+        assert_eq!(diagnostics, Vec::new());
+        assert_eq!(type_checker.type_of(expr), expected);
+    }
+
+    fn check_diagnostics(input: &str, expected: Vec<Diagnostic>) {
+        assert_eq!(check(input).3, expected);
+    }
+
+    fn mismatch(expected: &str, found: &str, range: TextRange) -> Diagnostic {
+        Diagnostic::error("E0007", "mismatched types", range)
+            .with_label(format!("expected {}, found {}", expected, found))
+    }
+
+    fn fn_binding(stmts: &[Stmt], idx: usize) -> BindingIdx {
+        match stmts[idx] {
+            Stmt::FnDef { binding, .. } => binding,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn infer_literal() {
+        check_ty("92", Ty::BigInt);
+    }
+
+    #[test]
+    fn infer_comparison() {
+        check_ty("1 + 2 < 4", Ty::Bool);
+    }
+
+    #[test]
+    fn infer_logical_expr() {
+        check_ty("!true || 1 == 2", Ty::Bool);
+    }
+
+    #[test]
+    fn infer_variable_from_value() {
+        check_ty("let a = 5 > 3\na", Ty::Bool);
+    }
+
+    #[test]
+    fn infer_block_without_tail() {
+        check_ty("{ let a = 1 }", Ty::Unit);
+    }
+
+    #[test]
+    fn infer_assignment() {
+        check_ty("let mut a = 1\na += 2", Ty::Unit);
+    }
+
+    #[test]
+    fn infer_if_else() {
+        check_ty("if true { 1 } else { 2 }", Ty::BigInt);
+    }
+
+    #[test]
+    fn infer_fn_from_body() {
+        let (_, stmts, type_checker, diagnostics) = check("fn less(x, y) { x < y }");
+
+        assert_eq!(diagnostics, Vec::new());
+        assert_eq!(
+            type_checker.type_of_binding(fn_binding(&stmts, 0)),
+            Ty::Fn {
+                params: vec![Ty::BigInt, Ty::BigInt],
+                ret: Box::new(Ty::Bool),
+            },
+        );
+    }
+
+    #[test]
+    fn infer_call() {
+        check_ty("fn not(b) { !b }\nnot(false)", Ty::Bool);
+    }
+
+    #[test]
+    fn infer_recursive_fn() {
+        check_ty(
+            "fn fact(n) { if n == 0 { 1 } else { n * fact(n - 1) } }\nfact(5)",
+            Ty::BigInt,
+        );
+    }
+
+    #[test]
+    fn infer_mutually_recursive_fns() {
+        let (_, stmts, type_checker, diagnostics) = check(
+            "fn even(n) { if n == 0 { true } else { odd(n - 1) } }
+fn odd(n) { if n == 0 { false } else { even(n - 1) } }",
+        );
+        let fn_ty = Ty::Fn {
+            params: vec![Ty::BigInt],
+            ret: Box::new(Ty::Bool),
+        };
+
+        assert_eq!(diagnostics, Vec::new());
+        assert_eq!(type_checker.type_of_binding(fn_binding(&stmts, 0)), fn_ty);
+        assert_eq!(type_checker.type_of_binding(fn_binding(&stmts, 1)), fn_ty);
+    }
+
+    #[test]
+    fn generic_fn_can_be_used_with_different_types() {
+        check_ty("fn id(x) { x }\nlet a = id(1) + 1\nid(true)", Ty::Bool);
+    }
+
+    #[test]
+    fn generic_fn_is_generalized_before_later_fn_defs() {
+        check_ty(
+            "fn id(x) { x }\nlet a = id(1)\nfn g() { 1 }\nid(true)",
+            Ty::Bool,
+        );
+    }
+
+    #[test]
+    fn fn_is_generalized_after_the_fns_it_uses() {
+        check_ty(
+            "fn twice(x) { id(id(x)) }\nlet a = twice(1)\nfn id(x) { x }\ntwice(true)",
+            Ty::Bool,
+        );
+    }
+
+    #[test]
+    fn generic_fn_ty_keeps_its_var() {
+        let (_, stmts, type_checker, _) = check("fn id(x) { x }");
+
+        match type_checker.type_of_binding(fn_binding(&stmts, 0)) {
+            Ty::Fn { params, ret } => {
+                assert!(matches!(params[..], [Ty::Var(_)]));
+                assert_eq!(params[0], *ret);
+            }
+            ty => panic!("expected a function type, found {}", ty),
+        }
+    }
+
+    #[test]
+    fn use_before_definition_constrains_fn() {
+        check_diagnostics(
+            "let a = f(true)\nfn f(x) { x + 1 }",
+            vec![mismatch("{integer}", "Bool", range(26, 27))],
+        );
+    }
+
+    #[test]
+    fn report_mismatched_operand() {
+        check_diagnostics("1 + true", vec![mismatch("{integer}", "Bool", range(4, 8))]);
+    }
+
+    #[test]
+    fn report_mismatched_condition() {
+        check_diagnostics(
+            "while 1 { }",
+            vec![mismatch("Bool", "{integer}", range(6, 7))],
+        );
+    }
+
+    #[test]
+    fn report_mismatched_if_branches() {
+        check_diagnostics(
+            "if true { 1 } else { false }",
+            vec![mismatch("{integer}", "Bool", range(19, 28))],
+        );
+    }
+
+    #[test]
+    fn if_without_else_must_be_unit() {
+        check_diagnostics(
+            "if true { 1 }",
+            vec![mismatch("()", "{integer}", range(8, 13))],
+        );
+    }
+
+    #[test]
+    fn report_mismatched_assignment() {
+        check_diagnostics(
+            "let mut a = 1\na = true",
+            vec![mismatch("{integer}", "Bool", range(18, 22))],
+        );
+    }
+
+    #[test]
+    fn report_mismatched_argument() {
+        check_diagnostics(
+            "fn f(x) { x + 1 }\nf(1 < 2)",
+            vec![mismatch("{integer}", "Bool", range(20, 25))],
+        );
+    }
+
+    #[test]
+    fn report_call_of_non_fn() {
+        check_diagnostics(
+            "let a = 1\na()",
+            vec![Diagnostic::error(
+                "E0007",
+                "cannot call a value of type {integer}",
+                range(10, 11),
+            )
+            .with_label("not a function")],
+        );
+    }
+
+    #[test]
+    fn report_wrong_argument_count() {
+        check_diagnostics(
+            "fn f(x) { x }\nf(1, 2)",
+            vec![Diagnostic::error(
+                "E0008",
+                "function takes 1 argument but 2 were supplied",
+                range(14, 21),
+            )
+            .with_label("expected 1 argument")],
+        );
+    }
+
+    #[test]
+    fn report_infinite_ty() {
+        let (_, _, _, diagnostics) = check("fn f(x) { x(x) }");
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "E0007");
+        assert_eq!(diagnostics[0].range, range(12, 13));
+    }
+
+    #[test]
+    fn infer_annotated_variable() {
+        check_ty("let a: Bool = 1 < 2\na", Ty::Bool);
+    }
+
+    #[test]
+    fn infer_fn_from_annotations() {
+        let (_, stmts, type_checker, diagnostics) = check("fn f(x: Bool, y) { y }");
+
+        assert_eq!(diagnostics, Vec::new());
+        match type_checker.type_of_binding(fn_binding(&stmts, 0)) {
+            Ty::Fn { params, ret } => {
+                assert_eq!(params[0], Ty::Bool);
+                assert_eq!(params[1], *ret);
+            }
+            ty => panic!("expected a function type, found {}", ty),
+        }
+    }
+
+    #[test]
+    fn report_value_that_disagrees_with_annotation() {
+        check_diagnostics(
+            "let a: i64 = true",
+            vec![mismatch("i64", "Bool", range(13, 17))],
+        );
+    }
+
+    #[test]
+    fn annotation_applies_to_later_assignments() {
+        check_diagnostics(
+            "let mut a: Bool = true\na = 1",
+            vec![mismatch("Bool", "{integer}", range(27, 28))],
+        );
+    }
+
+    #[test]
+    fn report_param_used_against_annotation() {
+        check_diagnostics(
+            "fn f(x: Bool) { x + 1 }",
+            vec![mismatch("{integer}", "Bool", range(16, 17))],
+        );
+    }
+
+    #[test]
+    fn report_argument_that_disagrees_with_annotation() {
+        check_diagnostics(
+            "let a = f(true)\nfn f(x: i64) { x }",
+            vec![mismatch("i64", "Bool", range(10, 14))],
+        );
+    }
+
+    #[test]
+    fn infer_literal_with_suffix() {
+        check_ty("5u8", Ty::Int(IntTy::U8));
+    }
+
+    #[test]
+    fn infer_literal_type_from_annotation() {
+        check_ty("let a: u16 = 5\na", Ty::Int(IntTy::U16));
+    }
+
+    #[test]
+    fn infer_literal_type_from_other_operand() {
+        check_ty("1 + 2u32", Ty::Int(IntTy::U32));
+    }
+
+    #[test]
+    fn infer_int_param_from_call() {
+        check_ty("fn inc(x) { x + 1 }\ninc(3u8)", Ty::Int(IntTy::U8));
+    }
+
+    #[test]
+    fn report_mismatched_int_types() {
+        check_diagnostics("1u8 + 1i8", vec![mismatch("u8", "i8", range(6, 9))]);
+    }
+
+    #[test]
+    fn int_params_are_not_generic() {
+        check_diagnostics(
+            "fn inc(x) { x + 1 }\ninc(1u8)\ninc(1i8)",
+            vec![mismatch("u8", "i8", range(33, 36))],
+        );
+    }
+
+    #[test]
+    fn report_literal_out_of_range_for_inferred_type() {
+        check_diagnostics(
+            "let a: u8 = 256",
+            vec![
+                Diagnostic::error("E0002", "literal out of range for ‘u8’", range(12, 15))
+                    .with_label("this number doesn’t fit in ‘u8’")
+                    .with_note("‘u8’ ranges from 0 to 255"),
+            ],
+        );
+    }
+
+    #[test]
+    fn negated_literal_is_checked_as_a_whole() {
+        check_diagnostics("let a: i8 = -128", Vec::new());
+        check_diagnostics("let a: i8 = -(128)", Vec::new());
+        assert_eq!(check("let a: i8 = -129").3.len(), 1);
+    }
+
+    #[test]
+    fn default_type_has_no_bounds() {
+        check_diagnostics(
+            "-123456789012345678901234567890123456789012345678901234567890",
+            Vec::new(),
+        );
+    }
+
+    #[test]
+    fn infer_big_int_from_annotation() {
+        check_ty(
+            "let a: Int = 5
+a + 1",
+            Ty::BigInt,
+        );
+    }
+
+    #[test]
+    fn report_mismatched_big_int_and_fixed_width_int() {
+        check_diagnostics(
+            "fn f(x: Int) { x }
+f(5u64)",
+            vec![mismatch("Int", "u64", range(21, 25))],
+        );
+    }
+
+    #[test]
+    fn report_literal_larger_than_i128_for_fixed_width_type() {
+        assert_eq!(
+            check("let a: u64 = 123456789012345678901234567890123456789012345678901234567890")
+                .3
+                .len(),
+            1,
+        );
+    }
+
+    #[test]
+    fn report_negation_of_unsigned_int() {
+        check_diagnostics(
+            "fn f(x: u8) { -x }",
+            vec![
+                Diagnostic::error("E0007", "cannot negate a value of type u8", range(14, 16))
+                    .with_label("unsigned integers can’t be negated"),
+            ],
+        );
+    }
+
+    #[test]
+    fn unknown_annotations_are_not_reported() {
+        check_diagnostics("let a: Num = true\na + 1", Vec::new());
+    }
+
+    #[test]
+    fn missing_exprs_are_not_reported() {
+        check_diagnostics("let a = (1 +)\nundefined + a", Vec::new());
+    }
+
+    #[test]
+    fn check_in_context_of_earlier_inputs() {
+        let mut db = Database::default();
+        let mut type_checker = TypeChecker::default();
+
+        let stmts = lower(&mut db, "let a = true");
+        assert_eq!(type_checker.check_stmts(&db, &stmts), Vec::new());
+
+        let stmts = lower(&mut db, "a + 1");
+        assert_eq!(
+            type_checker.check_stmts(&db, &stmts),
+            vec![mismatch("{integer}", "Bool", range(0, 1))],
+        );
+    }
+}
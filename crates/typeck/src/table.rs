@@ -0,0 +1,337 @@
+use crate::{Ty, TyVar};
+
+/// The type a binding was given. Functions are generalized over the type variables that nothing
+/// outside them constrains, so that each use of the function gets its own copy of those
+/// variables.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Scheme {
+    vars: Vec<TyVar>,
+    ty: Ty,
+}
+
+impl Scheme {
+    pub(crate) fn mono(ty: Ty) -> Self {
+        Self {
+            vars: Vec::new(),
+            ty,
+        }
+    }
+
+    pub(crate) fn ty(&self) -> &Ty {
+        &self.ty
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum VarState {
+    /// `level` is the number of function definitions enclosing the place the variable was
+    /// created in, lowered whenever the variable is unified with a type from further out.
+    Unbound {
+        level: u32,
+        /// whether only integer types can solve the variable
+        int: bool,
+    },
+    Bound(Ty),
+}
+
+/// The solutions found so far for each type variable.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Table {
+    vars: Vec<VarState>,
+}
+
+impl Table {
+    pub(crate) fn new_var(&mut self, level: u32) -> Ty {
+        Ty::Var(self.push_var(level, false))
+    }
+
+    pub(crate) fn new_int_var(&mut self, level: u32) -> Ty {
+        Ty::IntVar(self.push_var(level, true))
+    }
+
+    fn push_var(&mut self, level: u32, int: bool) -> TyVar {
+        let var = TyVar(self.vars.len() as u32);
+        self.vars.push(VarState::Unbound { level, int });
+
+        var
+    }
+
+    /// Solves every integer variable that is still unsolved with `Int`.
+    pub(crate) fn default_int_vars(&mut self) {
+        for state in &mut self.vars {
+            if let VarState::Unbound { int: true, .. } = state {
+                *state = VarState::Bound(Ty::BigInt);
+            }
+        }
+    }
+
+    /// Replaces every type variable in `ty` that has been solved with its solution.
+    pub(crate) fn resolve(&self, ty: &Ty) -> Ty {
+        match self.shallow_resolve(ty) {
+            Ty::Fn { params, ret } => Ty::Fn {
+                params: params.iter().map(|param| self.resolve(param)).collect(),
+                ret: Box::new(self.resolve(&ret)),
+            },
+            ty => ty,
+        }
+    }
+
+    /// Resolves `ty` only as far as needed to find out what kind of type it is.
+    pub(crate) fn shallow_resolve(&self, ty: &Ty) -> Ty {
+        let mut ty = ty.clone();
+
+        while let Ty::Var(var) | Ty::IntVar(var) = ty {
+            match &self.vars[var.0 as usize] {
+                VarState::Bound(solution) => ty = solution.clone(),
+                VarState::Unbound { .. } => break,
+            }
+        }
+
+        ty
+    }
+
+    /// Makes `a` and `b` the same type by solving the type variables in them, failing if they
+    /// are incompatible.
+    pub(crate) fn unify(&mut self, a: &Ty, b: &Ty) -> Result<(), ()> {
+        match (self.shallow_resolve(a), self.shallow_resolve(b)) {
+            (Ty::Unknown, _) | (_, Ty::Unknown) => Ok(()),
+            (Ty::Var(a), Ty::Var(b)) if a == b => Ok(()),
+            (Ty::Var(var), ty) | (ty, Ty::Var(var)) => self.bind(var, ty),
+            (Ty::IntVar(a), Ty::IntVar(b)) if a == b => Ok(()),
+            (Ty::IntVar(var), ty @ (Ty::Int(_) | Ty::BigInt))
+            | (ty @ (Ty::Int(_) | Ty::BigInt), Ty::IntVar(var))
+            | (Ty::IntVar(var), ty @ Ty::IntVar(_)) => self.bind(var, ty),
+            (Ty::Int(a), Ty::Int(b)) if a == b => Ok(()),
+            (Ty::BigInt, Ty::BigInt) | (Ty::Bool, Ty::Bool) | (Ty::Unit, Ty::Unit) => Ok(()),
+            (
+                Ty::Fn {
+                    params: a_params,
+                    ret: a_ret,
+                },
+                Ty::Fn {
+                    params: b_params,
+                    ret: b_ret,
+                },
+            ) if a_params.len() == b_params.len() => {
+                for (a, b) in a_params.iter().zip(&b_params) {
+                    self.unify(a, b)?;
+                }
+
+                self.unify(&a_ret, &b_ret)
+            }
+            _ => Err(()),
+        }
+    }
+
+    fn bind(&mut self, var: TyVar, ty: Ty) -> Result<(), ()> {
+        let level = match self.vars[var.0 as usize] {
+            VarState::Unbound { level, .. } => level,
+            VarState::Bound(_) => unreachable!(),
+        };
+
+        // A variable can’t be solved with a type that contains it, since that type would be
+        // infinitely large.
+        let mut free_vars = Vec::new();
+        self.free_vars(&ty, &mut free_vars);
+
+        if free_vars.contains(&var) {
+            return Err(());
+        }
+
+        // The variables in `ty` now have to be as visible as `var` is, so they can’t be
+        // generalized any deeper than it can.
+        for free_var in free_vars {
+            if let VarState::Unbound {
+                level: free_level, ..
+            } = &mut self.vars[free_var.0 as usize]
+            {
+                *free_level = (*free_level).min(level);
+            }
+        }
+
+        self.vars[var.0 as usize] = VarState::Bound(ty);
+
+        Ok(())
+    }
+
+    /// Turns `ty` into a scheme that is generic over every unsolved variable in it that was
+    /// created inside a function definition at a deeper level than `level`. Integer variables
+    /// are never generalized, since each expression has a single integer type when evaluated.
+    pub(crate) fn generalize(&self, ty: &Ty, level: u32) -> Scheme {
+        let mut free_vars = Vec::new();
+        self.free_vars(ty, &mut free_vars);
+
+        let vars = free_vars
+            .into_iter()
+            .filter(|var| match self.vars[var.0 as usize] {
+                VarState::Unbound {
+                    level: var_level, ..
+                } => var_level > level,
+                VarState::Bound(_) => false,
+            })
+            .collect();
+
+        Scheme {
+            vars,
+            ty: self.resolve(ty),
+        }
+    }
+
+    /// Gives a copy of `scheme`’s type with fresh variables in place of the ones it is generic
+    /// over.
+    pub(crate) fn instantiate(&mut self, scheme: &Scheme, level: u32) -> Ty {
+        let substitutions: Vec<_> = scheme
+            .vars
+            .iter()
+            .map(|var| (*var, self.new_var(level)))
+            .collect();
+
+        substitute(&self.resolve(&scheme.ty), &substitutions)
+    }
+
+    fn free_vars(&self, ty: &Ty, free_vars: &mut Vec<TyVar>) {
+        match self.shallow_resolve(ty) {
+            Ty::Var(var) => {
+                if !free_vars.contains(&var) {
+                    free_vars.push(var);
+                }
+            }
+            Ty::Fn { params, ret } => {
+                for param in &params {
+                    self.free_vars(param, free_vars);
+                }
+
+                self.free_vars(&ret, free_vars);
+            }
+            // Integer variables aren’t collected, since they are never generalized and can only
+            // be solved with integer types, which can’t contain a variable.
+            Ty::Unknown | Ty::Int(_) | Ty::BigInt | Ty::Bool | Ty::Unit | Ty::IntVar(_) => {}
+        }
+    }
+}
+
+fn substitute(ty: &Ty, substitutions: &[(TyVar, Ty)]) -> Ty {
+    match ty {
+        Ty::Var(var) => substitutions
+            .iter()
+            .find(|(substituted, _)| substituted == var)
+            .map_or_else(|| ty.clone(), |(_, replacement)| replacement.clone()),
+        Ty::Fn { params, ret } => Ty::Fn {
+            params: params
+                .iter()
+                .map(|param| substitute(param, substitutions))
+                .collect(),
+            ret: Box::new(substitute(ret, substitutions)),
+        },
+        Ty::Unknown | Ty::Int(_) | Ty::BigInt | Ty::Bool | Ty::Unit | Ty::IntVar(_) => ty.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hir::IntTy;
+
+    #[test]
+    fn unify_var_with_concrete_ty() {
+        let mut table = Table::default();
+        let var = table.new_var(0);
+
+        assert_eq!(table.unify(&var, &Ty::Int(IntTy::I64)), Ok(()));
+        assert_eq!(table.resolve(&var), Ty::Int(IntTy::I64));
+    }
+
+    #[test]
+    fn occurs_check() {
+        let mut table = Table::default();
+        let var = table.new_var(0);
+        let fn_ty = Ty::Fn {
+            params: vec![var.clone()],
+            ret: Box::new(Ty::Int(IntTy::I64)),
+        };
+
+        assert_eq!(table.unify(&var, &fn_ty), Err(()));
+    }
+
+    #[test]
+    fn fns_with_different_arities_dont_unify() {
+        let mut table = Table::default();
+        let a = Ty::Fn {
+            params: vec![Ty::Int(IntTy::I64)],
+            ret: Box::new(Ty::Int(IntTy::I64)),
+        };
+        let b = Ty::Fn {
+            params: Vec::new(),
+            ret: Box::new(Ty::Int(IntTy::I64)),
+        };
+
+        assert_eq!(table.unify(&a, &b), Err(()));
+    }
+
+    #[test]
+    fn generalize_only_deeper_vars() {
+        let mut table = Table::default();
+        let outer = table.new_var(0);
+        let inner = table.new_var(1);
+        let fn_ty = Ty::Fn {
+            params: vec![outer.clone(), inner],
+            ret: Box::new(Ty::Unit),
+        };
+
+        let scheme = table.generalize(&fn_ty, 0);
+        let a = table.instantiate(&scheme, 0);
+        let b = table.instantiate(&scheme, 0);
+
+        table.unify(&a, &fn_ty).unwrap();
+        assert_eq!(
+            table.unify(
+                &b,
+                &Ty::Fn {
+                    params: vec![Ty::Int(IntTy::I64), Ty::Bool],
+                    ret: Box::new(Ty::Unit),
+                },
+            ),
+            Ok(()),
+        );
+        assert_eq!(table.resolve(&outer), Ty::Int(IntTy::I64));
+    }
+
+    #[test]
+    fn binding_lowers_levels() {
+        let mut table = Table::default();
+        let outer = table.new_var(0);
+        let inner = table.new_var(1);
+        table.unify(&outer, &inner).unwrap();
+
+        let scheme = table.generalize(&inner, 0);
+
+        assert_eq!(scheme, Scheme::mono(table.resolve(&inner)));
+    }
+
+    #[test]
+    fn int_var_only_unifies_with_ints() {
+        let mut table = Table::default();
+        let var = table.new_int_var(0);
+
+        assert_eq!(table.unify(&var, &Ty::Bool), Err(()));
+        assert_eq!(table.unify(&var, &Ty::Int(IntTy::U8)), Ok(()));
+        assert_eq!(table.resolve(&var), Ty::Int(IntTy::U8));
+
+        let var = table.new_int_var(0);
+
+        assert_eq!(table.unify(&var, &Ty::BigInt), Ok(()));
+        assert_eq!(table.unify(&var, &Ty::Int(IntTy::U8)), Err(()));
+    }
+
+    #[test]
+    fn unsolved_int_vars_default_to_big_int() {
+        let mut table = Table::default();
+        let var = table.new_var(0);
+        let int_var = table.new_int_var(0);
+        table.unify(&var, &int_var).unwrap();
+
+        table.default_int_vars();
+
+        assert_eq!(table.resolve(&var), Ty::BigInt);
+    }
+}
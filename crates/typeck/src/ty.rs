@@ -0,0 +1,90 @@
+use hir::{IntTy, TypeRef};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ty {
+    /// The type of an expression that is missing or couldn’t be checked. It is compatible with
+    /// every other type so that the original error isn’t followed by more errors about the same
+    /// code.
+    Unknown,
+    Int(IntTy),
+    /// `Int`, whose values can be arbitrarily large
+    BigInt,
+    Bool,
+    /// The type of statements, loops and blocks that don’t end in an expression
+    Unit,
+    Fn {
+        params: Vec<Ty>,
+        ret: Box<Ty>,
+    },
+    /// A type that hasn’t been inferred yet, or a type parameter of a function that can be called
+    /// with arguments of any type
+    Var(TyVar),
+    /// An integer type that hasn’t been inferred yet, such as the type of a literal without a
+    /// suffix. If nothing determines which integer type it is, it defaults to `Int`.
+    IntVar(TyVar),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TyVar(pub(crate) u32);
+
+impl From<TypeRef> for Ty {
+    fn from(type_ref: TypeRef) -> Self {
+        match type_ref {
+            TypeRef::Missing => Self::Unknown,
+            TypeRef::Int(ty) => Self::Int(ty),
+            TypeRef::BigInt => Self::BigInt,
+            TypeRef::Bool => Self::Bool,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => write!(f, "{{unknown}}"),
+            Self::Int(ty) => write!(f, "{}", ty),
+            Self::BigInt => write!(f, "Int"),
+            Self::Bool => write!(f, "Bool"),
+            Self::Unit => write!(f, "()"),
+            Self::Fn { params, ret } => {
+                write!(f, "fn(")?;
+
+                for (idx, param) in params.iter().enumerate() {
+                    if idx != 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "{}", param)?;
+                }
+
+                write!(f, ") -> {}", ret)
+            }
+            Self::Var(TyVar(idx)) => write!(f, "?T{}", idx),
+            Self::IntVar(_) => write!(f, "{{integer}}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_fn_ty() {
+        let ty = Ty::Fn {
+            params: vec![
+                Ty::Int(IntTy::U8),
+                Ty::BigInt,
+                Ty::Var(TyVar(3)),
+                Ty::IntVar(TyVar(4)),
+            ],
+            ret: Box::new(Ty::Fn {
+                params: Vec::new(),
+                ret: Box::new(Ty::Unit),
+            }),
+        };
+
+        assert_eq!(ty.to_string(), "fn(u8, Int, ?T3, {integer}) -> fn() -> ()");
+    }
+}
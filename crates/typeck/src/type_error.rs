@@ -0,0 +1,87 @@
+use crate::Ty;
+use diagnostics::Diagnostic;
+use hir::IntTy;
+use std::fmt;
+use text_size::TextRange;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TypeError {
+    pub(crate) kind: TypeErrorKind,
+    pub(crate) range: TextRange,
+}
+
+impl TypeError {
+    pub(crate) fn into_diagnostic(self) -> Diagnostic {
+        let diagnostic = Diagnostic::error(self.kind.code(), self.kind.to_string(), self.range);
+
+        match self.kind {
+            TypeErrorKind::Mismatch { expected, found } => {
+                diagnostic.with_label(format!("expected {}, found {}", expected, found))
+            }
+            TypeErrorKind::NotCallable { .. } => diagnostic.with_label("not a function"),
+            TypeErrorKind::CannotNegate { .. } => {
+                diagnostic.with_label("unsigned integers can’t be negated")
+            }
+            TypeErrorKind::LiteralOutOfRange { ty } => diagnostic
+                .with_label(format!("this number doesn’t fit in ‘{}’", ty))
+                .with_note(format!("‘{}’ ranges from {} to {}", ty, ty.min(), ty.max(),)),
+            TypeErrorKind::WrongArgumentCount { expected, .. } => diagnostic.with_label(format!(
+                "expected {} argument{}",
+                expected,
+                if expected == 1 { "" } else { "s" },
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TypeErrorKind {
+    Mismatch {
+        expected: Ty,
+        found: Ty,
+    },
+    NotCallable {
+        found: Ty,
+    },
+    CannotNegate {
+        found: Ty,
+    },
+    WrongArgumentCount {
+        expected: usize,
+        found: usize,
+    },
+    /// A literal without a suffix that doesn’t fit in the type inferred for it. Literals with a
+    /// suffix are checked during validation.
+    LiteralOutOfRange {
+        ty: IntTy,
+    },
+}
+
+impl TypeErrorKind {
+    fn code(&self) -> &'static str {
+        match self {
+            Self::Mismatch { .. } | Self::NotCallable { .. } | Self::CannotNegate { .. } => "E0007",
+            Self::WrongArgumentCount { .. } => "E0008",
+            Self::LiteralOutOfRange { .. } => "E0002",
+        }
+    }
+}
+
+impl fmt::Display for TypeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatch { .. } => write!(f, "mismatched types"),
+            Self::NotCallable { found } => write!(f, "cannot call a value of type {}", found),
+            Self::CannotNegate { found } => write!(f, "cannot negate a value of type {}", found),
+            Self::WrongArgumentCount { expected, found } => write!(
+                f,
+                "function takes {} argument{} but {} {} supplied",
+                expected,
+                if *expected == 1 { "" } else { "s" },
+                found,
+                if *found == 1 { "was" } else { "were" },
+            ),
+            Self::LiteralOutOfRange { ty } => write!(f, "literal out of range for ‘{}’", ty),
+        }
+    }
+}
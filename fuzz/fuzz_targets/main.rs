@@ -8,6 +8,7 @@ fuzz_target!(|data: &[u8]| {
         let syntax = parse.syntax();
         let _validation_errors = ast::validation::validate(&syntax);
         let root = ast::Root::cast(syntax).unwrap();
-        let (_database, _stmts) = hir::lower(root);
+        let (database, stmts, _lowering_errors) = hir::lower(root);
+        let (_type_checker, _type_errors) = typeck::check(&database, &stmts);
     }
 });